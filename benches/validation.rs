@@ -0,0 +1,62 @@
+use attendx_backend::{
+    utils::{lang::Lang, locale_utils::Messages},
+    validations::{email::validate_email, name::validate_name, password::validate_password},
+};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use rayon::prelude::*;
+
+fn validators(c: &mut Criterion) {
+    let messages = Messages::new(Lang::En);
+
+    c.bench_function("validate_name", |b| {
+        b.iter(|| validate_name(black_box("Anne-Marie O'Brien"), &messages))
+    });
+    c.bench_function("validate_password", |b| {
+        b.iter(|| validate_password(black_box("Securepassword123."), &messages))
+    });
+    c.bench_function("validate_email", |b| {
+        b.iter(|| validate_email(black_box("anne.marie@example.com"), &messages))
+    });
+}
+
+/// The validators run up to a dozen string checks each; this compares
+/// dispatching checks of that size sequentially and through the rayon pool.
+fn dispatch(c: &mut Criterion) {
+    let checks: [fn(&str) -> bool; 12] = [
+        |s| !s.is_empty(),
+        |s| s.len() >= 3,
+        |s| s.len() <= 254,
+        |s| s.contains('@'),
+        |s| !s.starts_with('.'),
+        |s| !s.ends_with('.'),
+        |s| !s.contains(".."),
+        |s| !s.contains(' '),
+        |s| s.is_ascii(),
+        |s| s.matches('@').count() == 1,
+        |s| s.rsplit('.').next().is_some_and(|tld| tld.len() >= 2),
+        |s| s.chars().any(char::is_alphabetic),
+    ];
+    let input = "anne.marie@example.com";
+
+    let mut group = c.benchmark_group("dispatch");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            checks
+                .iter()
+                .filter(|check| !check(black_box(input)))
+                .count()
+        })
+    });
+    group.bench_function("rayon", |b| {
+        b.iter(|| {
+            checks
+                .par_iter()
+                .filter(|check| !check(black_box(input)))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, validators, dispatch);
+criterion_main!(benches);
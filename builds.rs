@@ -3,24 +3,108 @@ use reqwest::Client;
 use serde_json::{Map, Value, json};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    fs,
+    env, fs,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
-use tokio::sync::Semaphore;
+use tokio::{sync::Semaphore, time::sleep};
 use walkdir::WalkDir;
 
-const TARGET_LANGS: [&str; 3] = ["de", "id", "ja"];
+#[allow(dead_code)]
+#[path = "src/utils/lang.rs"]
+mod lang;
+
+use lang::Lang;
+
+const DEFAULT_TRANSLATE_URL: &str = "http://localhost:5000/translate";
 const SOURCE_DIR: &str = "locales/en";
 const OUTPUT_DIR: &str = "locales";
-const MAX_CONCURRENT_TRANSLATIONS: usize = 10;
+const DEFAULT_MAX_CONCURRENT_TRANSLATIONS: usize = 10;
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Settings read from `TRANSLATE_*` env vars, overridden by `--url`, `--langs`,
+/// `--concurrency` and `--retries` command line arguments.
+struct Config {
+    url: String,
+    target_langs: Vec<String>,
+    max_concurrent: usize,
+    max_retries: u32,
+}
+
+impl Config {
+    fn load() -> Result<Self, String> {
+        let mut url = env::var("TRANSLATE_URL").ok();
+        let mut langs = env::var("TRANSLATE_TARGET_LANGS").ok();
+        let mut concurrency = env::var("TRANSLATE_CONCURRENCY").ok();
+        let mut retries = env::var("TRANSLATE_RETRIES").ok();
+
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let slot = match arg.as_str() {
+                "--url" => &mut url,
+                "--langs" => &mut langs,
+                "--concurrency" => &mut concurrency,
+                "--retries" => &mut retries,
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            };
+            *slot = Some(
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?,
+            );
+        }
+
+        let target_langs: Vec<String> = match langs {
+            Some(langs) => langs
+                .split(',')
+                .map(str::trim)
+                .filter(|lang| !lang.is_empty())
+                .map(str::to_string)
+                .collect(),
+            None => Lang::all()
+                .iter()
+                .filter(|lang| **lang != Lang::En)
+                .map(|lang| lang.as_code().to_string())
+                .collect(),
+        };
+        if target_langs.is_empty() {
+            return Err("No target languages given".to_string());
+        }
+
+        let max_concurrent = match concurrency {
+            Some(value) => value
+                .parse::<usize>()
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("Invalid concurrency: {}", value))?,
+            None => DEFAULT_MAX_CONCURRENT_TRANSLATIONS,
+        };
+
+        let max_retries = match retries {
+            Some(value) => value
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid retry count: {}", value))?,
+            None => DEFAULT_MAX_RETRIES,
+        };
+
+        Ok(Self {
+            url: url.unwrap_or_else(|| DEFAULT_TRANSLATE_URL.to_string()),
+            target_langs,
+            max_concurrent,
+            max_retries,
+        })
+    }
+}
 
 async fn fetch_translation(
     client: &Client,
+    url: &str,
     text: &str,
     target_lang: &str,
-) -> Result<String, reqwest::Error> {
-    let url = "http://localhost:5000/translate";
+) -> Result<String, BoxError> {
     let payload = json!({
         "q": text,
         "source": "en",
@@ -33,10 +117,33 @@ async fn fetch_translation(
         .header("Content-Type", "application/json")
         .json(&payload)
         .send()
-        .await?;
+        .await?
+        .error_for_status()?;
 
     let body: Value = res.json().await?;
-    Ok(body["translatedText"].as_str().unwrap_or(text).to_string())
+    body["translatedText"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Response is missing translatedText".into())
+}
+
+async fn translate_with_retry(
+    client: &Client,
+    config: &Config,
+    text: &str,
+    target_lang: &str,
+) -> Option<String> {
+    for attempt in 0..=config.max_retries {
+        match fetch_translation(client, &config.url, text, target_lang).await {
+            Ok(translated) => return Some(translated),
+            Err(e) if attempt < config.max_retries => {
+                sleep(RETRY_DELAY * (attempt + 1)).await;
+                eprintln!("Retrying {:?} ({}): {}", text, target_lang, e);
+            }
+            Err(e) => eprintln!("Failed to translate {:?} ({}): {}", text, target_lang, e),
+        }
+    }
+    None
 }
 
 fn flatten_json(value: &Value, prefix: String, map: &mut BTreeMap<String, String>) {
@@ -80,9 +187,10 @@ fn unflatten_json(flat: &BTreeMap<String, String>) -> Value {
 
 async fn translate_file(
     client: &Client,
+    config: Arc<Config>,
     semaphore: Arc<Semaphore>,
     file_path: &Path,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<HashMap<String, usize>, BoxError> {
     let file_content = fs::read_to_string(file_path)?;
     let json: Value = serde_json::from_str(&file_content)?;
     let mut flat_map = BTreeMap::new();
@@ -90,36 +198,44 @@ async fn translate_file(
 
     let unique_texts: HashSet<String> = flat_map.values().cloned().collect();
     let mut translations: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    let mut failures: HashMap<String, usize> = HashMap::new();
 
-    for &lang in &TARGET_LANGS {
+    for lang in &config.target_langs {
         let mut text_map = HashMap::new();
         let mut futures = FuturesUnordered::new();
 
         for text in unique_texts.iter() {
             let client = client.clone();
+            let config = config.clone();
             let text = text.clone();
-            let lang = lang.to_string();
+            let lang = lang.clone();
             let sem = semaphore.clone();
             futures.push(async move {
                 let _permit = sem.acquire_owned().await.unwrap();
-                let translated = fetch_translation(&client, &text, &lang)
-                    .await
-                    .unwrap_or(text.clone());
+                let translated = translate_with_retry(&client, &config, &text, &lang).await;
                 (text, translated)
             });
         }
 
         while let Some((orig, trans)) = futures.next().await {
-            text_map.insert(orig, trans);
+            match trans {
+                Some(trans) => {
+                    text_map.insert(orig, trans);
+                }
+                None => {
+                    *failures.entry(lang.clone()).or_default() += 1;
+                    text_map.insert(orig.clone(), orig);
+                }
+            }
         }
 
         translations.insert(lang, text_map);
     }
 
-    for &lang in &TARGET_LANGS {
+    for lang in &config.target_langs {
         let mut flat_translated = BTreeMap::new();
         for (k, v) in &flat_map {
-            let translated = translations[lang].get(v).unwrap_or(v);
+            let translated = translations[lang.as_str()].get(v).unwrap_or(v);
             flat_translated.insert(k.clone(), translated.clone());
         }
 
@@ -137,7 +253,7 @@ async fn translate_file(
         }
     }
 
-    Ok(())
+    Ok(failures)
 }
 
 fn find_json_files(dir: &str) -> Vec<PathBuf> {
@@ -153,23 +269,41 @@ fn find_json_files(dir: &str) -> Vec<PathBuf> {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Arc::new(Config::load()?);
     let client = Client::new();
-    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_TRANSLATIONS));
+    let semaphore = Arc::new(Semaphore::new(config.max_concurrent));
     let files = find_json_files(SOURCE_DIR);
 
     let tasks = files.into_iter().map(|file| {
         let client = client.clone();
+        let config = config.clone();
         let semaphore = semaphore.clone();
         async move {
             println!("Translating {:?}", file);
-            if let Err(e) = translate_file(&client, semaphore, &file).await {
-                eprintln!("Error translating {:?}: {}", file, e);
+            match translate_file(&client, config, semaphore, &file).await {
+                Ok(failures) => failures,
+                Err(e) => {
+                    eprintln!("Error translating {:?}: {}", file, e);
+                    HashMap::new()
+                }
             }
         }
     });
 
-    futures::future::join_all(tasks).await;
+    let mut failures: BTreeMap<String, usize> = BTreeMap::new();
+    for file_failures in futures::future::join_all(tasks).await {
+        for (lang, count) in file_failures {
+            *failures.entry(lang).or_default() += count;
+        }
+    }
 
-    println!("✅ All translations saved to locales/[de,id,ja]/");
+    println!(
+        "✅ All translations saved to locales/[{}]/",
+        config.target_langs.join(",")
+    );
+    for lang in &config.target_langs {
+        let failed = failures.get(lang).copied().unwrap_or_default();
+        println!("   {}: {} string(s) failed to translate", lang, failed);
+    }
     Ok(())
 }
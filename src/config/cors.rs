@@ -1,10 +1,208 @@
 use actix_cors::Cors;
+use actix_web::http::Method;
+use std::str::FromStr;
 
-pub fn configure_cors() -> Cors {
-    Cors::default()
-        .allow_any_origin()
-        .allow_any_header()
-        .allow_any_method()
-        .supports_credentials()
-        .max_age(3600)
+use crate::constants::{CORS_ALLOW_CREDENTIALS, CORS_ALLOWED_METHODS, CORS_ALLOWED_ORIGINS};
+
+const WILDCARD: &str = "*";
+const MAX_AGE_SECS: usize = 3600;
+
+#[derive(Debug, Clone)]
+pub struct CorsSettings {
+    /// `None` means any origin is allowed, which has to be requested with `*`.
+    origins: Option<Vec<String>>,
+    /// Empty means any method is allowed.
+    methods: Vec<Method>,
+    allow_credentials: bool,
+}
+
+impl CorsSettings {
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_values(
+            &CORS_ALLOWED_ORIGINS,
+            &CORS_ALLOWED_METHODS,
+            &CORS_ALLOW_CREDENTIALS,
+        )
+    }
+
+    /// Parses the raw `CORS_ALLOWED_ORIGINS`, `CORS_ALLOWED_METHODS` and
+    /// `CORS_ALLOW_CREDENTIALS` values.
+    pub fn from_values(
+        origins: &str,
+        methods: &str,
+        allow_credentials: &str,
+    ) -> Result<Self, String> {
+        let origins = split_list(origins);
+        let methods = split_list(methods);
+        let allow_credentials = parse_flag(allow_credentials)?;
+
+        let any_origin = origins.iter().any(|origin| origin == WILDCARD);
+        if any_origin && allow_credentials {
+            return Err(
+                "CORS_ALLOWED_ORIGINS cannot be a wildcard when CORS_ALLOW_CREDENTIALS is enabled"
+                    .to_string(),
+            );
+        }
+
+        let origins = if any_origin { None } else { Some(origins) };
+
+        let methods = if methods.iter().any(|method| method == WILDCARD) {
+            Vec::new()
+        } else {
+            methods
+                .iter()
+                .map(|method| {
+                    Method::from_str(&method.to_ascii_uppercase())
+                        .map_err(|_| format!("Invalid method in CORS_ALLOWED_METHODS: {method}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Self {
+            origins,
+            methods,
+            allow_credentials,
+        })
+    }
+}
+
+pub fn configure_cors(settings: &CorsSettings) -> Cors {
+    let mut cors = Cors::default().allow_any_header().max_age(MAX_AGE_SECS);
+
+    cors = match &settings.origins {
+        None => cors.allow_any_origin(),
+        Some(origins) => origins
+            .iter()
+            .fold(cors, |cors, origin| cors.allowed_origin(origin)),
+    };
+
+    cors = if settings.methods.is_empty() {
+        cors.allow_any_method()
+    } else {
+        cors.allowed_methods(settings.methods.clone())
+    };
+
+    if settings.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+
+    cors
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_flag(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" | "" => Ok(false),
+        other => Err(format!("Invalid value for CORS_ALLOW_CREDENTIALS: {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        App, HttpResponse,
+        http::{StatusCode, header},
+        test::{TestRequest, call_service, init_service},
+        web,
+    };
+
+    async fn cors_response(request: TestRequest) -> actix_web::dev::ServiceResponse {
+        let settings = CorsSettings::from_env().expect("the defaults are valid");
+        let app = init_service(
+            App::new()
+                .wrap(configure_cors(&settings))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        call_service(&app, request.uri("/").to_request())
+            .await
+            .map_into_boxed_body()
+    }
+
+    #[actix_rt::test]
+    async fn an_allowed_origin_is_echoed_back() {
+        let response = cors_response(
+            TestRequest::get().insert_header((header::ORIGIN, "http://localhost:3000")),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&header::HeaderValue::from_static("http://localhost:3000"))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn a_disallowed_origin_gets_no_allow_origin_header() {
+        let response = cors_response(
+            TestRequest::get().insert_header((header::ORIGIN, "https://evil.example")),
+        )
+        .await;
+
+        assert!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+    }
+
+    #[actix_rt::test]
+    async fn a_preflight_from_a_disallowed_origin_is_rejected() {
+        let response = cors_response(
+            TestRequest::default()
+                .method(Method::OPTIONS)
+                .insert_header((header::ORIGIN, "https://evil.example"))
+                .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "GET")),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn the_default_settings_let_browsers_send_the_session_cookie() {
+        let settings = CorsSettings::from_env().expect("the defaults are valid");
+
+        assert!(settings.allow_credentials);
+        assert!(settings.origins.is_some());
+    }
+
+    #[test]
+    fn a_wildcard_origin_is_refused_when_credentials_are_allowed() {
+        let err = CorsSettings::from_values("*", "GET", "true").unwrap_err();
+
+        assert_eq!(
+            err,
+            "CORS_ALLOWED_ORIGINS cannot be a wildcard when CORS_ALLOW_CREDENTIALS is enabled"
+        );
+        assert!(CorsSettings::from_values("https://app.example, *", "GET", "1").is_err());
+    }
+
+    #[test]
+    fn a_wildcard_origin_is_allowed_without_credentials() {
+        let settings = CorsSettings::from_values("*", "*", "false").expect("valid settings");
+
+        assert!(settings.origins.is_none());
+        assert!(settings.methods.is_empty());
+        assert!(!settings.allow_credentials);
+    }
 }
@@ -1,19 +1,139 @@
 use crate::{
-    constants::{DB_NAME, MONGODB_URI, USER_COL_NAME},
-    models::user_model::User,
+    constants::{
+        ATTENDANCE_COL_NAME, DB_NAME, IDEMPOTENCY_KEYS_COL_NAME, MIGRATIONS_COL_NAME, MONGODB_URI,
+        ORGANIZATION_MEMBERS_COL_NAME, ORGANIZATIONS_COL_NAME, PASSWORD_RESET_TOKENS_COL_NAME,
+        USER_COL_NAME,
+    },
+    models::{
+        attendance_model::Attendance, idempotency_key_model::IdempotencyKey,
+        organization_member_model::OrganizationMember,
+        password_reset_token_model::PasswordResetToken, user_model::User,
+    },
+    utils::auth_utils::normalize_email,
 };
+use actix_rt::time::sleep;
+use futures_util::stream::TryStreamExt;
+use log::{debug, error, info, warn};
 use mongodb::{
     Client, Collection, IndexModel,
-    bson::doc,
-    error::Error as MongoError,
+    bson::{DateTime, Document, doc},
+    error::{
+        Error as MongoError, ErrorKind, InsertManyError, RETRYABLE_WRITE_ERROR,
+        TRANSIENT_TRANSACTION_ERROR, WriteFailure,
+    },
     options::{ClientOptions, IndexOptions},
 };
+use std::{collections::HashSet, env, str::FromStr, time::Duration};
+
+const DUPLICATE_KEY_CODE: i32 = 11000;
+const DEFAULT_READ_RETRIES: u32 = 2;
+const READ_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+const DEFAULT_CONNECT_ATTEMPTS: u32 = 5;
+const INITIAL_CONNECT_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_secs(10);
 
 pub async fn connect_to_database() -> Result<Client, MongoError> {
+    let max_attempts = env_option("MONGODB_CONNECT_MAX_ATTEMPTS")
+        .unwrap_or(DEFAULT_CONNECT_ATTEMPTS)
+        .max(1);
+
+    retry_with_backoff(max_attempts, INITIAL_CONNECT_BACKOFF, try_connect).await
+}
+
+async fn try_connect() -> Result<Client, MongoError> {
     let client_uri = (*MONGODB_URI).as_str();
 
-    let client_options = ClientOptions::parse(client_uri).await?;
-    Client::with_options(client_options)
+    let mut client_options = ClientOptions::parse(client_uri).await?;
+    apply_pool_options(&mut client_options);
+    let client = Client::with_options(client_options)?;
+
+    client
+        .database("admin")
+        .run_command(doc! { "ping": 1 })
+        .await?;
+    Ok(client)
+}
+
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    mut operation: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut delay = initial_delay;
+    let mut attempt = 1;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= max_attempts => {
+                error!("❌ Database connection attempt {attempt}/{max_attempts} failed: {err}");
+                return Err(err);
+            }
+            Err(err) => {
+                warn!(
+                    "⚠️ Database connection attempt {attempt}/{max_attempts} failed: {err}. Retrying in {delay:?}"
+                );
+                sleep(delay).await;
+                delay = (delay * 2).min(MAX_CONNECT_BACKOFF);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+pub fn apply_pool_options(client_options: &mut ClientOptions) {
+    if let Some(min_pool_size) = env_option("MONGODB_MIN_POOL_SIZE") {
+        client_options.min_pool_size = Some(min_pool_size);
+    }
+    if let Some(max_pool_size) = env_option("MONGODB_MAX_POOL_SIZE") {
+        client_options.max_pool_size = Some(max_pool_size);
+    }
+    if let Some(timeout_ms) = env_option("MONGODB_CONNECT_TIMEOUT_MS") {
+        client_options.connect_timeout = Some(Duration::from_millis(timeout_ms));
+    }
+}
+
+pub async fn with_retry<T, F, Fut>(mut operation: F) -> Result<T, MongoError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, MongoError>>,
+{
+    let retries = env_option("MONGODB_READ_RETRIES").unwrap_or(DEFAULT_READ_RETRIES);
+    let mut attempt = 0;
+
+    loop {
+        match operation().await {
+            Err(err) if attempt < retries && is_transient_error(&err) => {
+                attempt += 1;
+                warn!("⚠️ Transient MongoDB error, retrying read ({attempt}/{retries}): {err}");
+                sleep(READ_RETRY_DELAY * attempt).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient_error(err: &MongoError) -> bool {
+    err.contains_label(TRANSIENT_TRANSACTION_ERROR)
+        || err.contains_label(RETRYABLE_WRITE_ERROR)
+        || matches!(
+            err.kind.as_ref(),
+            ErrorKind::Io(_)
+                | ErrorKind::ConnectionPoolCleared { .. }
+                | ErrorKind::ServerSelection { .. }
+        )
+}
+
+fn env_option<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
 }
 
 pub async fn get_collection<T>(
@@ -49,8 +169,442 @@ pub async fn create_unique_indexes(client: &Client) -> Result<(), MongoError> {
 
     create_partial_unique_index(&collection, "email").await?;
     create_partial_unique_index(&collection, "username").await?;
-    create_partial_unique_index(&collection, "nim").await?;
-    create_partial_unique_index(&collection, "nidn").await?;
 
     Ok(())
 }
+
+const FOLD_USER_EMAILS_MIGRATION: &str = "fold_user_emails";
+const COUNT_ORGANIZATION_MEMBERS_MIGRATION: &str = "count_organization_members";
+
+async fn migration_applied(
+    migrations: &Collection<Document>,
+    name: &str,
+) -> Result<bool, MongoError> {
+    Ok(migrations.find_one(doc! { "_id": name }).await?.is_some())
+}
+
+/// Marks a one-off migration as done. Another instance recording it first
+/// is fine, the work was the same.
+async fn record_migration(migrations: &Collection<Document>, name: &str) -> Result<(), MongoError> {
+    match migrations
+        .insert_one(doc! { "_id": name, "applied_at": DateTime::now() })
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(err) if is_duplicate_key_error(&err) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// One-off migration rewriting stored emails into the case-folded key
+/// `normalize_email` produces, moving the original casing to `email_display`.
+/// Completion is recorded in the migrations collection, so later startups
+/// return `None` without scanning the users.
+pub async fn normalize_user_emails(client: &Client) -> Result<Option<u64>, MongoError> {
+    let migrations = get_collection::<Document>(client, &MIGRATIONS_COL_NAME).await?;
+    if migration_applied(&migrations, FOLD_USER_EMAILS_MIGRATION).await? {
+        return Ok(None);
+    }
+
+    let collection = get_collection::<Document>(client, &USER_COL_NAME).await?;
+    let mut cursor = collection
+        .find(doc! { "email": { "$type": "string" } })
+        .projection(doc! { "email": 1, "email_display": 1 })
+        .await?;
+
+    let mut migrated = 0;
+    while let Some(user) = cursor.try_next().await? {
+        let (Ok(id), Ok(email)) = (user.get_object_id("_id"), user.get_str("email")) else {
+            continue;
+        };
+        let Some(canonical) = migrated_email(email) else {
+            continue;
+        };
+
+        let mut update = doc! { "email": &canonical };
+        let typed = email.trim();
+        if user.get_str("email_display").is_err() && typed != canonical {
+            update.insert("email_display", typed);
+        }
+
+        match collection
+            .update_one(doc! { "_id": id }, doc! { "$set": update })
+            .await
+        {
+            Ok(_) => {
+                info!("📧 Normalized email {} to {}", email, canonical);
+                migrated += 1;
+            }
+            Err(err) if is_duplicate_key_error(&err) => warn!(
+                "⚠️ Email {} not normalized: {} belongs to another account",
+                email, canonical
+            ),
+            Err(err) => return Err(err),
+        }
+    }
+
+    record_migration(&migrations, FOLD_USER_EMAILS_MIGRATION).await?;
+    Ok(Some(migrated))
+}
+
+/// The canonical email for a stored account, or `None` when it already is.
+fn migrated_email(email: &str) -> Option<String> {
+    let canonical = normalize_email(email);
+
+    (canonical != email).then_some(canonical)
+}
+
+/// One-off migration filling in `member_count` for organizations created
+/// before joins reserved their slots through it. Returns how many
+/// organizations have members, or `None` once the migration has run.
+pub async fn count_organization_members(client: &Client) -> Result<Option<u64>, MongoError> {
+    let migrations = get_collection::<Document>(client, &MIGRATIONS_COL_NAME).await?;
+    if migration_applied(&migrations, COUNT_ORGANIZATION_MEMBERS_MIGRATION).await? {
+        return Ok(None);
+    }
+
+    let organizations = get_collection::<Document>(client, &ORGANIZATIONS_COL_NAME).await?;
+    let members = get_collection::<Document>(client, &ORGANIZATION_MEMBERS_COL_NAME).await?;
+
+    organizations
+        .update_many(doc! {}, doc! { "$set": { "member_count": 0 } })
+        .await?;
+
+    let mut counts = members
+        .aggregate([doc! { "$group": { "_id": "$organization_id", "count": { "$sum": 1 } } }])
+        .await?;
+    let mut counted = 0;
+    while let Some(group) = counts.try_next().await? {
+        let (Ok(id), Ok(count)) = (group.get_object_id("_id"), group.get_i32("count")) else {
+            continue;
+        };
+        organizations
+            .update_one(
+                doc! { "_id": id },
+                doc! { "$set": { "member_count": count } },
+            )
+            .await?;
+        counted += 1;
+    }
+
+    record_migration(&migrations, COUNT_ORGANIZATION_MEMBERS_MIGRATION).await?;
+    Ok(Some(counted))
+}
+
+pub async fn create_member_indexes(client: &Client) -> Result<(), MongoError> {
+    let collection =
+        get_collection::<OrganizationMember>(client, &ORGANIZATION_MEMBERS_COL_NAME).await?;
+
+    let mut indexes: Vec<IndexModel> = ["nim", "nidn"]
+        .map(|identifier| {
+            let field = format!("identifiers.{identifier}");
+            IndexModel::builder()
+                .keys(doc! { "organization_id": 1, &field: 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .partial_filter_expression(
+                            doc! { &field: { "$exists": true, "$type": "string" } },
+                        )
+                        .build(),
+                )
+                .build()
+        })
+        .into();
+    indexes.push(
+        IndexModel::builder()
+            .keys(doc! { "organization_id": 1, "user_id": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { "user_id": { "$type": "objectId" } })
+                    .build(),
+            )
+            .build(),
+    );
+
+    collection.create_indexes(indexes).await?;
+    Ok(())
+}
+
+/// `created_at` is the timestamp attendance queries filter on. Creating an
+/// index that already exists with the same keys is a no-op, so this is safe to
+/// run on every startup.
+pub async fn create_attendance_indexes(client: &Client) -> Result<(), MongoError> {
+    let collection = get_collection::<Attendance>(client, &ATTENDANCE_COL_NAME).await?;
+    collection.create_indexes(attendance_indexes()).await?;
+    Ok(())
+}
+
+fn attendance_indexes() -> [IndexModel; 3] {
+    let by_organization = IndexModel::builder()
+        .keys(doc! { "organization_id": 1, "user_id": 1, "created_at": 1 })
+        .build();
+
+    let by_member = IndexModel::builder()
+        .keys(doc! { "user_id": 1, "created_at": 1 })
+        .build();
+
+    let one_check_in_per_class_day = IndexModel::builder()
+        .keys(doc! { "user_id": 1, "class_id": 1, "check_in_date": 1 })
+        .options(
+            IndexOptions::builder()
+                .unique(true)
+                .partial_filter_expression(doc! { "check_in_date": { "$exists": true } })
+                .build(),
+        )
+        .build();
+
+    [by_organization, by_member, one_check_in_per_class_day]
+}
+
+pub fn is_duplicate_key_error(err: &MongoError) -> bool {
+    match err.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == DUPLICATE_KEY_CODE
+        }
+        ErrorKind::InsertMany(InsertManyError {
+            write_errors: Some(write_errors),
+            ..
+        }) => write_errors
+            .iter()
+            .any(|write_error| write_error.code == DUPLICATE_KEY_CODE),
+        _ => false,
+    }
+}
+
+/// The positions of the documents an unordered `insert_many` skipped because of
+/// a unique index, or `None` when anything else went wrong.
+pub fn duplicate_key_indexes(err: &MongoError) -> Option<HashSet<usize>> {
+    match err.kind.as_ref() {
+        ErrorKind::InsertMany(InsertManyError {
+            write_errors: Some(write_errors),
+            write_concern_error: None,
+            ..
+        }) if write_errors
+            .iter()
+            .all(|write_error| write_error.code == DUPLICATE_KEY_CODE) =>
+        {
+            Some(
+                write_errors
+                    .iter()
+                    .map(|write_error| write_error.index)
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+pub async fn create_search_indexes(client: &Client) -> Result<(), MongoError> {
+    let collection = get_collection::<User>(client, &USER_COL_NAME).await?;
+
+    let name = IndexModel::builder().keys(doc! { "name": 1 }).build();
+    collection.create_index(name).await?;
+
+    Ok(())
+}
+
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub async fn create_idempotency_indexes(client: &Client) -> Result<(), MongoError> {
+    let collection = get_collection::<IdempotencyKey>(client, &IDEMPOTENCY_KEYS_COL_NAME).await?;
+
+    // Keys used to be unique across all users; drop that index if it is still
+    // around so two users may pick the same key.
+    if let Err(err) = collection.drop_index("scope_1_key_1").await {
+        debug!("Legacy idempotency index not dropped: {}", err);
+    }
+
+    let unique_key = IndexModel::builder()
+        .keys(doc! { "user_id": 1, "scope": 1, "key": 1 })
+        .options(IndexOptions::builder().unique(true).build())
+        .build();
+
+    let expiry = IndexModel::builder()
+        .keys(doc! { "created_at": 1 })
+        .options(
+            IndexOptions::builder()
+                .expire_after(IDEMPOTENCY_KEY_TTL)
+                .build(),
+        )
+        .build();
+
+    collection.create_indexes([unique_key, expiry]).await?;
+    Ok(())
+}
+
+pub async fn create_password_reset_indexes(client: &Client) -> Result<(), MongoError> {
+    let collection =
+        get_collection::<PasswordResetToken>(client, &PASSWORD_RESET_TOKENS_COL_NAME).await?;
+
+    let user = IndexModel::builder().keys(doc! { "user_id": 1 }).build();
+
+    let expiry = IndexModel::builder()
+        .keys(doc! { "expires_at": 1 })
+        .options(IndexOptions::builder().expire_after(Duration::ZERO).build())
+        .build();
+
+    collection.create_indexes([user, expiry]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, io};
+
+    fn insert_many_error(codes: &[i32]) -> MongoError {
+        let write_errors: Vec<_> = codes
+            .iter()
+            .enumerate()
+            .map(|(index, code)| doc! { "index": index as i32 * 2, "code": code, "errmsg": "failed" })
+            .collect();
+        let failure: InsertManyError = bson::from_document(doc! { "writeErrors": write_errors })
+            .expect("an insert_many failure");
+        MongoError::from(ErrorKind::InsertMany(failure))
+    }
+
+    #[test]
+    fn duplicate_key_indexes_lists_the_rejected_documents() {
+        let err = insert_many_error(&[DUPLICATE_KEY_CODE, DUPLICATE_KEY_CODE]);
+
+        assert_eq!(duplicate_key_indexes(&err), Some(HashSet::from([0, 2])));
+    }
+
+    #[test]
+    fn duplicate_key_indexes_ignores_other_failures() {
+        let err = insert_many_error(&[DUPLICATE_KEY_CODE, 121]);
+
+        assert_eq!(duplicate_key_indexes(&err), None);
+    }
+
+    #[actix_rt::test]
+    async fn with_retry_retries_a_transient_failure_once() {
+        let calls = Cell::new(0);
+
+        let result = with_retry(|| {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move {
+                if attempt == 1 {
+                    Err(MongoError::from(io::ErrorKind::ConnectionReset))
+                } else {
+                    Ok("found")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "found");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn with_retry_returns_other_failures_immediately() {
+        let calls = Cell::new(0);
+
+        let result: Result<(), _> = with_retry(|| {
+            calls.set(calls.get() + 1);
+            async { Err(insert_many_error(&[DUPLICATE_KEY_CODE])) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn pool_options_are_read_from_the_environment() {
+        // SAFETY: no other test reads these variables.
+        unsafe {
+            env::set_var("MONGODB_MIN_POOL_SIZE", "2");
+            env::set_var("MONGODB_MAX_POOL_SIZE", "25");
+            env::set_var("MONGODB_CONNECT_TIMEOUT_MS", "1500");
+        }
+        let mut client_options = ClientOptions::parse("mongodb://127.0.0.1:9").await.unwrap();
+
+        apply_pool_options(&mut client_options);
+
+        assert_eq!(client_options.min_pool_size, Some(2));
+        assert_eq!(client_options.max_pool_size, Some(25));
+        assert_eq!(
+            client_options.connect_timeout,
+            Some(Duration::from_millis(1500))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn retry_with_backoff_succeeds_on_the_third_attempt() {
+        let calls = Cell::new(0);
+
+        let result = retry_with_backoff(5, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move {
+                if attempt < 3 {
+                    Err(format!("attempt {attempt} refused"))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[actix_rt::test]
+    async fn retry_with_backoff_gives_up_after_the_last_attempt() {
+        let calls = Cell::new(0);
+
+        let result: Result<(), String> = retry_with_backoff(2, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            async { Err("refused".to_string()) }
+        })
+        .await;
+
+        assert_eq!(result, Err("refused".to_string()));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn stored_emails_are_migrated_to_the_canonical_form() {
+        assert_eq!(migrated_email("user@example.com"), None);
+        assert_eq!(
+            migrated_email("User@Example.COM").as_deref(),
+            Some("user@example.com")
+        );
+        assert_eq!(
+            migrated_email(" Mixed.Case@example.com").as_deref(),
+            Some("mixed.case@example.com")
+        );
+    }
+
+    #[test]
+    fn attendance_queries_have_a_compound_index() {
+        let keys: Vec<_> = attendance_indexes()
+            .into_iter()
+            .map(|index| index.keys)
+            .collect();
+
+        assert!(keys.contains(&doc! { "organization_id": 1, "user_id": 1, "created_at": 1 }));
+    }
+
+    #[actix_rt::test]
+    #[ignore = "needs a MongoDB reachable at MONGODB_URI"]
+    async fn attendance_indexes_exist_after_startup() {
+        let client = connect_to_database().await.unwrap();
+
+        // Runs twice, as two restarts would, to show creation is idempotent.
+        create_attendance_indexes(&client).await.unwrap();
+        create_attendance_indexes(&client).await.unwrap();
+
+        let collection = get_collection::<Attendance>(&client, &ATTENDANCE_COL_NAME)
+            .await
+            .unwrap();
+        let names = collection.list_index_names().await.unwrap();
+        assert!(names.contains(&"organization_id_1_user_id_1_created_at_1".to_string()));
+    }
+}
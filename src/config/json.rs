@@ -0,0 +1,57 @@
+use actix_web::{error::JsonPayloadError, web};
+
+use crate::{
+    constants::{JSON_BODY_LIMIT_BYTES, PAYLOAD_BODY_LIMIT_BYTES},
+    types::responses::api_error::ApiError,
+    utils::locale_utils::{Messages, get_lang},
+};
+
+const DEFAULT_JSON_BODY_LIMIT: usize = 64 * 1024;
+const DEFAULT_PAYLOAD_BODY_LIMIT: usize = 256 * 1024;
+
+pub fn json_body_limit() -> usize {
+    JSON_BODY_LIMIT_BYTES
+        .parse()
+        .unwrap_or(DEFAULT_JSON_BODY_LIMIT)
+}
+
+pub fn payload_body_limit() -> usize {
+    PAYLOAD_BODY_LIMIT_BYTES
+        .parse()
+        .unwrap_or(DEFAULT_PAYLOAD_BODY_LIMIT)
+}
+
+/// Limit for raw bodies (`String`/`Bytes` extractors) such as CSV imports.
+/// Handlers take the body as a `Result` and turn overflows into
+/// [`payload_too_large`], since `PayloadConfig` has no error handler.
+pub fn configure_payload() -> web::PayloadConfig {
+    web::PayloadConfig::new(payload_body_limit())
+}
+
+pub fn payload_too_large(messages: &Messages) -> ApiError {
+    ApiError::PayloadTooLarge(messages.get_validation_message(
+        "request.payload_too_large",
+        "The request body is too large.",
+    ))
+}
+
+pub fn configure_json() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(json_body_limit())
+        .error_handler(|err, req| {
+            let messages = Messages::new(get_lang(req));
+
+            match &err {
+                JsonPayloadError::OverflowKnownLength { .. }
+                | JsonPayloadError::Overflow { .. } => payload_too_large(&messages),
+                _ => ApiError::bad_request(
+                    messages.get_validation_message(
+                        "request.invalid_json",
+                        "The request body is not valid JSON.",
+                    ),
+                    Some(err.to_string().into()),
+                ),
+            }
+            .into()
+        })
+}
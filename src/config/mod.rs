@@ -1,2 +1,3 @@
 pub mod cors;
 pub mod database;
+pub mod json;
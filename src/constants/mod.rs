@@ -10,6 +10,14 @@ macro_rules! lazy_env_var {
     };
 }
 
+macro_rules! lazy_env_var_or {
+    ($name:ident, $default:expr) => {
+        pub static $name: LazyLock<String> = LazyLock::new(|| {
+            env::var(stringify!($name)).unwrap_or_else(|_| $default.to_string())
+        });
+    };
+}
+
 lazy_env_var!(CLIENT_ID);
 lazy_env_var!(CLIENT_SECRET);
 lazy_env_var!(REDIRECT_URI);
@@ -21,3 +29,46 @@ lazy_env_var!(USER_COL_NAME);
 lazy_env_var!(CLASS_COL_NAME);
 lazy_env_var!(ATTENDANCE_COL_NAME);
 lazy_env_var!(ORGANIZATIONS_COL_NAME);
+
+lazy_env_var_or!(IDEMPOTENCY_KEYS_COL_NAME, "idempotency_keys");
+lazy_env_var_or!(ORGANIZATION_MEMBERS_COL_NAME, "organization_members");
+lazy_env_var_or!(AUDIT_LOGS_COL_NAME, "audit_logs");
+lazy_env_var_or!(PASSWORD_RESET_TOKENS_COL_NAME, "password_reset_tokens");
+lazy_env_var_or!(EXCUSE_REQUESTS_COL_NAME, "excuse_requests");
+lazy_env_var_or!(MIGRATIONS_COL_NAME, "migrations");
+
+lazy_env_var_or!(
+    COOKIE_SECURE,
+    if cfg!(debug_assertions) { "false" } else { "true" }
+);
+lazy_env_var_or!(
+    COOKIE_SAMESITE,
+    if cfg!(debug_assertions) { "lax" } else { "strict" }
+);
+lazy_env_var_or!(JWT_ISSUER, "attendx");
+lazy_env_var_or!(JWT_AUDIENCE, "attendx-client");
+lazy_env_var_or!(JWT_EXPIRY_SECS, "86400");
+lazy_env_var_or!(PASSWORD_RESET_EXPIRY_SECS, "1800");
+lazy_env_var_or!(EMAIL_VERIFICATION_EXPIRY_SECS, "86400");
+lazy_env_var_or!(REQUIRE_EMAIL_VERIFICATION, "false");
+// Where verification and password reset emails are posted for delivery.
+lazy_env_var_or!(MAIL_WEBHOOK_URL, "");
+lazy_env_var_or!(LOGIN_LOCKOUT_THRESHOLD, "5");
+lazy_env_var_or!(LOGIN_LOCKOUT_SECS, "900");
+lazy_env_var_or!(LATE_GRACE_MINUTES, "10");
+lazy_env_var_or!(ARGON2_MEMORY_KIB, "19456");
+lazy_env_var_or!(ARGON2_ITERATIONS, "2");
+lazy_env_var_or!(ARGON2_PARALLELISM, "1");
+lazy_env_var_or!(
+    CORS_ALLOWED_ORIGINS,
+    "http://localhost:3000,http://127.0.0.1:3000,http://localhost:5173"
+);
+lazy_env_var_or!(CORS_ALLOWED_METHODS, "GET,POST,PUT,PATCH,DELETE,OPTIONS");
+// Sessions live in a cookie, so browsers only send them when credentials are allowed.
+lazy_env_var_or!(CORS_ALLOW_CREDENTIALS, "true");
+lazy_env_var_or!(ADMIN_EMAILS, "");
+lazy_env_var_or!(TRUSTED_PROXIES, "");
+lazy_env_var_or!(DEFAULT_LANG, "en");
+lazy_env_var_or!(NAME_ALLOWED_PUNCTUATION, "'-’");
+lazy_env_var_or!(JSON_BODY_LIMIT_BYTES, "65536");
+lazy_env_var_or!(PAYLOAD_BODY_LIMIT_BYTES, "262144");
@@ -0,0 +1,361 @@
+use actix_web::{HttpRequest, HttpResponse, http::header, web};
+use bson::oid;
+use log::error;
+use std::sync::Arc;
+
+use crate::{
+    services::attendance_service::{
+        AttendanceService, AttendanceServiceError, MAX_BATCH_SIZE, window_message,
+    },
+    types::{
+        auth::authenticated_user::AuthenticatedUser,
+        requests::{
+            attendance::{
+                attendance_summary_query::AttendanceSummaryQuery,
+                batch_attendance_request::BatchAttendanceRequest,
+                class_attendance_request::ClassAttendanceRequest,
+                create_class_request::CreateClassRequest,
+                today_attendance_query::TodayAttendanceQuery,
+            },
+            common::pagination_query::PaginationQuery,
+        },
+        responses::{api_error::ApiError, api_response::ApiResponse},
+    },
+    utils::{
+        locale_utils::{Messages, get_lang},
+        validation_utils::validate_fields,
+    },
+    validations::name::validate_name,
+};
+
+pub async fn get_attendance_summary_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    org_id: web::Path<String>,
+    query: web::Query<AttendanceSummaryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let AttendanceSummaryQuery { from, to } = query.into_inner();
+
+    match attendance_service
+        .summary(&org_id, &user.claims.email, from, to)
+        .await
+    {
+        Ok(summary) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message(
+                "summary.success",
+                "Attendance summary fetched successfully.",
+            ),
+            summary,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(view_forbidden(&messages)),
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to fetch attendance summary: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "summary.failed",
+                    "Failed to fetch attendance summary.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn export_attendance_summary_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    org_id: web::Path<String>,
+    query: web::Query<AttendanceSummaryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let AttendanceSummaryQuery { from, to } = query.into_inner();
+
+    match attendance_service
+        .summary_csv(&org_id, &user.claims.email, from, to)
+        .await
+    {
+        Ok(csv) => Ok(HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"attendance-summary.csv\"",
+            ))
+            .body(csv)),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(view_forbidden(&messages)),
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to export attendance summary: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "export.failed",
+                    "Failed to export attendance summary.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn get_member_attendance_summary_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    member_id: web::Path<String>,
+    query: web::Query<AttendanceSummaryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let AttendanceSummaryQuery { from, to } = query.into_inner();
+
+    match attendance_service
+        .member_summary(&member_id, &user.claims.email, from, to)
+        .await
+    {
+        Ok(summary) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message(
+                "summary.member_success",
+                "Member attendance summary fetched successfully.",
+            ),
+            summary,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::MemberNotFound) => Err(ApiError::NotFound(
+                messages.get_member_message("fetch.member_not_found", "Member not found."),
+            )),
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(view_forbidden(&messages)),
+            _ if err.is::<oid::Error>() => Err(ApiError::bad_request(
+                messages.get_member_message("id.invalid", "Invalid member id."),
+                None,
+            )),
+            _ => {
+                error!("❌ Failed to fetch member attendance summary: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "summary.member_failed",
+                    "Failed to fetch member attendance summary.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn get_today_attendance_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    org_id: web::Path<String>,
+    query: web::Query<TodayAttendanceQuery>,
+    pagination: web::Query<PaginationQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match attendance_service
+        .today(
+            &org_id,
+            query.class_id.as_deref(),
+            &user.claims.email,
+            &pagination,
+        )
+        .await
+    {
+        Ok(members) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message(
+                "today.success",
+                "Today's attendance fetched successfully.",
+            ),
+            members,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::ClassNotFound) => Err(ApiError::NotFound(
+                messages.get_attendance_message("class.not_found", "Class not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(view_forbidden(&messages)),
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to fetch today's attendance: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "today.failed",
+                    "Failed to fetch today's attendance.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn record_attendance_batch_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    payload: web::Json<BatchAttendanceRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match attendance_service
+        .record_batch(payload.into_inner(), &user.claims.email, &messages)
+        .await
+    {
+        Ok(report) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message("batch.success", "Attendance batch processed."),
+            report,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::ClassNotFound) => Err(ApiError::NotFound(
+                messages.get_attendance_message("class.not_found", "Class not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(forbidden(&messages)),
+            Some(AttendanceServiceError::BatchTooLarge) => {
+                Err(ApiError::PayloadTooLarge(messages.get_attendance_message(
+                    "batch.too_large",
+                    &format!("A batch can contain at most {MAX_BATCH_SIZE} entries."),
+                )))
+            }
+            _ => {
+                error!("❌ Failed to record attendance batch: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "batch.failed",
+                    "Failed to record attendance batch.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn create_class_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    payload: web::Json<CreateClassRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = payload.into_inner();
+
+    validate_fields(vec![("name", data.name.trim(), validate_name)], &messages).map_err(
+        |errs| {
+            ApiError::validation(
+                errs,
+                messages.get_attendance_message("class.invalid_data", "Invalid class data."),
+            )
+        },
+    )?;
+
+    match attendance_service
+        .create_class(data, &user.claims.email)
+        .await
+    {
+        Ok(class_session) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            messages.get_attendance_message("class.created", "Class created."),
+            class_session,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(forbidden(&messages)),
+            Some(AttendanceServiceError::InvalidInstructor) => Err(ApiError::bad_request(
+                messages.get_attendance_message(
+                    "class.invalid_instructor",
+                    "The instructor must be an admin or staff member of the organization.",
+                ),
+                None,
+            )),
+            _ => {
+                error!("❌ Failed to create class: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "class.create_failed",
+                    "Failed to create class.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn mark_class_attendance_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    class_id: web::Path<String>,
+    payload: web::Json<ClassAttendanceRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match attendance_service
+        .mark_class_attendance(
+            &class_id,
+            &user.claims.email,
+            payload.into_inner(),
+            &messages,
+        )
+        .await
+    {
+        Ok(report) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message("class.success", "Class attendance recorded."),
+            report,
+        ))),
+        Err(err) => match err.downcast_ref::<AttendanceServiceError>() {
+            Some(AttendanceServiceError::ClassNotFound) => Err(ApiError::NotFound(
+                messages.get_attendance_message("class.not_found", "Class not found."),
+            )),
+            Some(AttendanceServiceError::Forbidden) => Err(forbidden(&messages)),
+            Some(
+                err @ (AttendanceServiceError::CheckInNotOpen
+                | AttendanceServiceError::CheckInClosed),
+            ) => Err(ApiError::Conflict(window_message(err, &messages))),
+            Some(AttendanceServiceError::BatchTooLarge) => {
+                Err(ApiError::PayloadTooLarge(messages.get_attendance_message(
+                    "batch.too_large",
+                    &format!("A batch can contain at most {MAX_BATCH_SIZE} entries."),
+                )))
+            }
+            _ => {
+                error!("❌ Failed to record class attendance: {:?}", err);
+                Err(ApiError::Internal(messages.get_attendance_message(
+                    "class.failed",
+                    "Failed to record class attendance.",
+                )))
+            }
+        },
+    }
+}
+
+fn view_forbidden(messages: &Messages) -> ApiError {
+    ApiError::Forbidden(messages.get_attendance_message(
+        "view.forbidden",
+        "You are not allowed to view attendance for this organization.",
+    ))
+}
+
+fn invalid_id(messages: &Messages) -> ApiError {
+    ApiError::bad_request(
+        messages.get_organization_message("id.invalid", "Invalid organization id."),
+        None,
+    )
+}
+
+fn forbidden(messages: &Messages) -> ApiError {
+    ApiError::Forbidden(messages.get_attendance_message(
+        "record.forbidden",
+        "You are not allowed to record attendance for this organization.",
+    ))
+}
@@ -1,45 +1,163 @@
 use actix_web::{HttpRequest, HttpResponse, web};
-use log::info;
-use std::sync::Arc;
+use log::{error, info};
+use serde_json::json;
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
 
 use crate::{
-    constants::COOKIE_NAME,
-    services::user_service::UserService,
+    services::{
+        password_reset_service::{PasswordResetService, PasswordResetServiceError},
+        user_service::{UserService, UserServiceError},
+    },
     types::{
-        requests::auth::{login_request::LoginRequest, register_request::RegisterRequest},
-        responses::api_response::ApiResponse,
+        auth::authenticated_user::AuthenticatedUser,
+        requests::auth::{
+            email_availability_query::EmailAvailabilityQuery, login_request::LoginRequest,
+            password_reset_request::PasswordResetRequest, register_request::RegisterRequest,
+            reset_password_request::ResetPasswordRequest, verify_email_query::VerifyEmailQuery,
+        },
+        responses::{
+            api_error::ApiError, api_response::ApiResponse,
+            email_availability_response::EmailAvailabilityResponse, user_response::UserResponse,
+        },
     },
     utils::{
-        auth_utils::generate_cookie,
+        auth_utils::{generate_cookie, generate_expired_cookie},
         locale_utils::{Messages, get_lang},
-        validation_utils::{
-            handle_internal_error, handle_validation_error, validate_login_data,
-            validate_register_data,
-        },
+        rate_limiter::{RateLimiter, client_key},
+        validation_utils::{validate_fields, validate_login_data, validate_register_data},
     },
+    validations::{email::validate_email, password::validate_password},
 };
 
+const EMAIL_CHECKS_PER_WINDOW: u32 = 10;
+const EMAIL_CHECK_WINDOW: Duration = Duration::from_secs(60);
+const EMAIL_CHECK_MAX_CLIENTS: usize = 10_000;
+
+static EMAIL_CHECK_LIMITER: LazyLock<RateLimiter> = LazyLock::new(|| {
+    RateLimiter::new(
+        EMAIL_CHECKS_PER_WINDOW,
+        EMAIL_CHECK_WINDOW,
+        EMAIL_CHECK_MAX_CLIENTS,
+    )
+});
+
 pub async fn register_user_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
     new_user: web::Json<RegisterRequest>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
     let data = new_user.into_inner();
 
-    if let Err(errs) = validate_register_data(&data, &messages) {
-        let err_msg =
-            messages.get_auth_message("register.invalid_data", "Invalid registration data");
-        return handle_validation_error(errs, &err_msg);
+    check_registration(&data, &messages)?;
+
+    let user = user_service
+        .register_user(data)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::success(
+        messages.get_auth_message("register.success", "User successfully created."),
+        UserResponse::from(user),
+    )))
+}
+
+/// Runs the registration checks without creating an account, so forms can be
+/// validated before they are submitted.
+pub async fn validate_registration_handler(
+    req: HttpRequest,
+    new_user: web::Json<RegisterRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    check_registration(&new_user, &messages)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_auth_message("register.valid", "Registration data is valid."),
+        None::<()>,
+    )))
+}
+
+fn check_registration(data: &RegisterRequest, messages: &Messages) -> Result<(), ApiError> {
+    validate_register_data(data, messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_auth_message("register.invalid_data", "Invalid registration data"),
+        )
+    })?;
+
+    if !data.subscription_plan.is_self_service() {
+        return Err(ApiError::bad_request(
+            messages.get_auth_message(
+                "register.plan_not_allowed",
+                "The selected subscription plan cannot be chosen during registration.",
+            ),
+            Some(json!({ "field": "subscription_plan" })),
+        ));
     }
 
-    match user_service.register_user(data, &messages).await {
-        Ok(user) => HttpResponse::Created().json(ApiResponse::success(
-            messages.get_auth_message("register.success", "User successfully created."),
-            user,
+    Ok(())
+}
+
+pub async fn email_available_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    query: web::Query<EmailAvailabilityQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    if !EMAIL_CHECK_LIMITER.check(&client_key(&req)) {
+        return Err(ApiError::TooManyRequests(messages.get_auth_message(
+            "email_available.rate_limited",
+            "Too many requests. Please try again later.",
+        )));
+    }
+
+    validate_fields(vec![("email", &query.email, validate_email)], &messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_validation_message("email.invalid", "The provided email is invalid"),
+        )
+    })?;
+
+    let available = user_service
+        .is_email_available(&query.email)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_auth_message("email_available.success", "Email availability checked."),
+        EmailAvailabilityResponse { available },
+    )))
+}
+
+pub async fn verify_email_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    query: web::Query<VerifyEmailQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match user_service.verify_email(&query.token).await {
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_auth_message("verify.success", "Email verified successfully."),
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::bad_request(
+            messages.get_auth_message(
+                "verify.invalid_token",
+                "The verification link is invalid or has expired.",
+            ),
+            None,
         )),
-        Err(err) => handle_internal_error(err),
+        Err(err) => Err(err.to_api_error(&messages)),
     }
 }
 
@@ -47,53 +165,245 @@ pub async fn jwt_login_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
     credentials: web::Json<LoginRequest>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
     let data = credentials.into_inner();
 
-    if let Err(errs) = validate_login_data(&data, &messages) {
-        let err_msg =
-            messages.get_auth_message("login.invalid_credentials", "Invalid login credentials");
-        return handle_validation_error(errs, &err_msg);
-    }
+    validate_login_data(&data, &messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_auth_message("login.invalid_credentials", "Invalid login credentials"),
+        )
+    })?;
 
-    match user_service
-        .authenticate_user(&data.email, &data.password, &messages)
+    let (user, token) = user_service
+        .authenticate_user(&data.identifier, &data.password)
         .await
-    {
-        Ok((user, token)) => {
-            info!("User {} successfully logged in.", data.email);
-            let cookie = generate_cookie(token);
-            HttpResponse::Ok().cookie(cookie).json(ApiResponse::success(
-                messages.get_auth_message("login.success", "Login successful"),
-                user,
-            ))
-        }
-        Err(err) => {
-            HttpResponse::Unauthorized().json(ApiResponse::<()>::error(err.to_string(), None))
-        }
-    }
+        .map_err(|err| match err {
+            UserServiceError::NotFound => ApiError::Unauthorized(err.to_message(&messages)),
+            err => err.to_api_error(&messages),
+        })?;
+
+    info!("User {} successfully logged in.", user.email);
+    let cookie = generate_cookie(token);
+    Ok(HttpResponse::Ok().cookie(cookie).json(ApiResponse::success(
+        messages.get_auth_message("login.success", "Login successful"),
+        UserResponse::from(user),
+    )))
 }
 
 pub async fn logout_user_handler(req: HttpRequest) -> HttpResponse {
-    use actix_web::cookie::{Cookie, SameSite, time::Duration};
-
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
 
-    let expired = Cookie::build(&*COOKIE_NAME, "")
-        .http_only(true)
-        .secure(true)
-        .same_site(SameSite::None)
-        .path("/")
-        .max_age(Duration::new(0, 0))
-        .finish();
-
     HttpResponse::Ok()
-        .cookie(expired)
+        .cookie(generate_expired_cookie())
         .json(ApiResponse::success(
             messages.get_auth_message("logout.success", "Logged out successfully."),
             None::<()>,
         ))
 }
+
+pub async fn revoke_sessions_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    user_service: web::Data<Arc<UserService>>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    user_service
+        .revoke_sessions(&user.claims.email)
+        .await
+        .map_err(|err| {
+            error!("❌ Failed to revoke sessions: {:?}", err);
+            ApiError::Internal(messages.get_auth_message(
+                "sessions.revoke_failed",
+                "Failed to sign out of all sessions.",
+            ))
+        })?;
+
+    Ok(HttpResponse::Ok()
+        .cookie(generate_expired_cookie())
+        .json(ApiResponse::success(
+            messages.get_auth_message("sessions.revoked", "Signed out of all sessions."),
+            None::<()>,
+        )))
+}
+
+pub async fn request_password_reset_handler(
+    req: HttpRequest,
+    password_reset_service: web::Data<Arc<PasswordResetService>>,
+    payload: web::Json<PasswordResetRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = payload.into_inner();
+
+    validate_fields(vec![("email", &data.email, validate_email)], &messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_validation_message("email.invalid", "The provided email is invalid"),
+        )
+    })?;
+
+    if let Err(err) = password_reset_service.request_reset(&data.email).await {
+        if let Some(PasswordResetServiceError::DeliveryUnavailable) =
+            err.downcast_ref::<PasswordResetServiceError>()
+        {
+            return Err(ApiError::Internal(messages.get_auth_message(
+                "password_reset.unavailable",
+                "Password reset by email is not available on this server.",
+            )));
+        }
+        error!("❌ Failed to issue password reset token: {:?}", err);
+        return Err(ApiError::Internal(messages.get_auth_message(
+            "password_reset.failed",
+            "Failed to process the password reset request.",
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_auth_message(
+            "password_reset.requested",
+            "If the email is registered, a password reset link has been sent.",
+        ),
+        None::<()>,
+    )))
+}
+
+pub async fn reset_password_handler(
+    req: HttpRequest,
+    password_reset_service: web::Data<Arc<PasswordResetService>>,
+    payload: web::Json<ResetPasswordRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = payload.into_inner();
+
+    validate_fields(
+        vec![("new_password", &data.new_password, validate_password)],
+        &messages,
+    )
+    .map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_user_message("password.invalid_data", "Invalid password data."),
+        )
+    })?;
+
+    match password_reset_service
+        .reset_password(&data.token, &data.new_password)
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_auth_message("password_reset.success", "Password reset successfully."),
+            None::<()>,
+        ))),
+        Err(err) => match err.downcast_ref::<PasswordResetServiceError>() {
+            Some(PasswordResetServiceError::InvalidToken) => Err(ApiError::bad_request(
+                messages.get_auth_message(
+                    "password_reset.invalid_token",
+                    "The password reset token is invalid or has expired.",
+                ),
+                None,
+            )),
+            _ => {
+                error!("❌ Failed to reset password: {:?}", err);
+                Err(ApiError::Internal(messages.get_auth_message(
+                    "password_reset.failed",
+                    "Failed to process the password reset request.",
+                )))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        repositories::in_memory_user_repository::{self, InMemoryUserRepository},
+        types::models::user::subscription::SubscriptionPlan,
+    };
+    use actix_web::{ResponseError, body::to_bytes, http::StatusCode, test::TestRequest};
+
+    async fn response_data(response: HttpResponse) -> serde_json::Value {
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["data"].clone()
+    }
+
+    #[actix_rt::test]
+    async fn register_returns_the_profile_without_the_password_hash() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let service = in_memory_user_repository::user_service(repository).await;
+        let request = RegisterRequest {
+            name: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            password: "Securepassword123.".to_string(),
+            subscription_plan: SubscriptionPlan::Free,
+        };
+
+        let response = register_user_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            web::Json(request),
+        )
+        .await
+        .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let data = response_data(response).await;
+        assert_eq!(data["email"], "jane@example.com");
+        assert!(data.get("password").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn login_with_a_wrong_password_is_rejected_with_401() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+        let service = in_memory_user_repository::user_service(repository).await;
+        let credentials = LoginRequest {
+            identifier: "jane@example.com".to_string(),
+            password: "Wrongpassword123.".to_string(),
+        };
+
+        let response = jwt_login_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            web::Json(credentials),
+        )
+        .await
+        .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.cookies().next().is_none());
+    }
+
+    #[actix_rt::test]
+    async fn login_returns_the_profile_without_the_password_hash() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+        let service = in_memory_user_repository::user_service(repository).await;
+        let credentials = LoginRequest {
+            identifier: "jane@example.com".to_string(),
+            password: "Securepassword123.".to_string(),
+        };
+
+        let response = jwt_login_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            web::Json(credentials),
+        )
+        .await
+        .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let data = response_data(response).await;
+        assert_eq!(data["email"], "jane@example.com");
+        assert!(data.get("password").is_none());
+        assert!(data.get("failed_login_attempts").is_none());
+    }
+}
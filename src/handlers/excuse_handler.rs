@@ -0,0 +1,136 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use log::error;
+use std::sync::Arc;
+
+use crate::{
+    services::excuse_service::{ExcuseService, ExcuseServiceError},
+    types::{
+        auth::authenticated_user::AuthenticatedUser,
+        requests::attendance::submit_excuse_request::SubmitExcuseRequest,
+        responses::{api_error::ApiError, api_response::ApiResponse},
+    },
+    utils::locale_utils::{Messages, get_lang},
+};
+
+const MAX_REASON_LENGTH: usize = 500;
+
+pub async fn submit_excuse_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    excuse_service: web::Data<Arc<ExcuseService>>,
+    payload: web::Json<SubmitExcuseRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = payload.into_inner();
+
+    let reason = data.reason.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(ApiError::bad_request(
+            messages.get_attendance_message(
+                "excuse.invalid_reason",
+                "The reason must be between 1 and 500 characters.",
+            ),
+            None,
+        ));
+    }
+
+    match excuse_service.submit_excuse(&user.claims.email, data).await {
+        Ok(excuse) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            messages.get_attendance_message("excuse.submitted", "Excuse request submitted."),
+            excuse,
+        ))),
+        Err(err) => Err(excuse_error(
+            err,
+            &messages,
+            messages
+                .get_attendance_message("excuse.submit_failed", "Failed to submit excuse request."),
+        )),
+    }
+}
+
+pub async fn approve_excuse_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    excuse_service: web::Data<Arc<ExcuseService>>,
+    excuse_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match excuse_service
+        .review_excuse(&excuse_id, &user.claims.email, true)
+        .await
+    {
+        Ok(excuse) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message("excuse.approved", "Excuse request approved."),
+            excuse,
+        ))),
+        Err(err) => Err(excuse_error(
+            err,
+            &messages,
+            messages
+                .get_attendance_message("excuse.review_failed", "Failed to review excuse request."),
+        )),
+    }
+}
+
+pub async fn reject_excuse_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    excuse_service: web::Data<Arc<ExcuseService>>,
+    excuse_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match excuse_service
+        .review_excuse(&excuse_id, &user.claims.email, false)
+        .await
+    {
+        Ok(excuse) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_attendance_message("excuse.rejected", "Excuse request rejected."),
+            excuse,
+        ))),
+        Err(err) => Err(excuse_error(
+            err,
+            &messages,
+            messages
+                .get_attendance_message("excuse.review_failed", "Failed to review excuse request."),
+        )),
+    }
+}
+
+fn excuse_error(err: anyhow::Error, messages: &Messages, failure: String) -> ApiError {
+    match err.downcast_ref::<ExcuseServiceError>() {
+        Some(ExcuseServiceError::OrganizationNotFound) => ApiError::NotFound(
+            messages
+                .get_attendance_message("excuse.organization_not_found", "Organization not found."),
+        ),
+        Some(ExcuseServiceError::NotMember) => {
+            ApiError::Forbidden(messages.get_attendance_message(
+                "excuse.not_member",
+                "You are not a member of this organization.",
+            ))
+        }
+        Some(ExcuseServiceError::Forbidden) => {
+            ApiError::Forbidden(messages.get_attendance_message(
+                "excuse.forbidden",
+                "You are not allowed to review excuses for this organization.",
+            ))
+        }
+        Some(ExcuseServiceError::ExcuseNotFound) => ApiError::NotFound(
+            messages.get_attendance_message("excuse.not_found", "Excuse request not found."),
+        ),
+        Some(ExcuseServiceError::AlreadyProcessed) => {
+            ApiError::Conflict(messages.get_attendance_message(
+                "excuse.already_processed",
+                "This excuse request has already been processed.",
+            ))
+        }
+        None => {
+            error!("❌ Failed to process excuse request: {:?}", err);
+            ApiError::Internal(failure)
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+
+use crate::{
+    types::responses::api_error::ApiError,
+    utils::locale_utils::{Lang, Messages, get_lang},
+};
+
+pub async fn get_locale_bundle_handler(
+    req: HttpRequest,
+    lang: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let Some(requested) = Lang::parse(&lang) else {
+        let messages = Messages::new(get_lang(&req));
+        return Err(ApiError::NotFound(messages.get_validation_message(
+            "lang.unsupported",
+            "Unsupported language.",
+        )));
+    };
+
+    Ok(HttpResponse::Ok().json(Messages::new(requested).bundle()))
+}
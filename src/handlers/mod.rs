@@ -1,3 +1,7 @@
+pub mod attendance_handler;
 pub mod auth_handler;
+pub mod excuse_handler;
+pub mod locale_handler;
 pub mod organization_handler;
+pub mod organization_member_handler;
 pub mod user_handler;
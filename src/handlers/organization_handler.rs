@@ -1,63 +1,459 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{HttpRequest, HttpResponse, web};
+use bson::{Document, oid};
+use log::error;
+use serde_json::json;
 use std::sync::Arc;
 
 use crate::{
-    models::organization_model::Organization, services::organization_service::OrganizationService,
+    services::organization_service::{OrganizationService, OrganizationServiceError},
+    types::{
+        auth::authenticated_user::AuthenticatedUser,
+        requests::{
+            common::pagination_query::PaginationQuery,
+            organization::{
+                create_organization_request::CreateOrganizationRequest,
+                organization_search_query::OrganizationSearchQuery,
+                transfer_ownership_request::TransferOwnershipRequest,
+                update_organization_request::UpdateOrganizationRequest,
+            },
+        },
+        responses::{
+            api_error::ApiError, api_response::ApiResponse, count_response::CountResponse,
+        },
+    },
+    utils::{
+        locale_utils::{Messages, get_lang},
+        validation_utils::{validate_organization_data, validate_organization_update},
+    },
 };
 
 pub async fn create_organization_handler(
+    req: HttpRequest,
     organization_service: web::Data<Arc<OrganizationService>>,
-    organization: web::Json<Organization>,
-) -> impl Responder {
+    auth_user: AuthenticatedUser,
+    organization: web::Json<CreateOrganizationRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let owner_email = auth_user.claims.email.as_str();
+    let data = organization.into_inner();
+
+    validate_organization_data(&data, &messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_organization_message("invalid_data", "Invalid organization data."),
+        )
+    })?;
+
+    let idempotency_key = req
+        .headers()
+        .get("Idempotency-Key")
+        .and_then(|value| value.to_str().ok());
+
     match organization_service
-        .create_organization(organization.into_inner())
+        .create_organization_idempotent(data, owner_email, idempotency_key)
         .await
     {
-        Ok(new_org) => HttpResponse::Created().json(new_org),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(new_org) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            messages.get_organization_message(
+                "create.success",
+                "Organization created successfully.",
+            ),
+            new_org,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OrganizationLimitReached) => Err(ApiError::Forbidden(
+                messages.get_user_message(
+                    "subscription.organization_limit_reached",
+                    "You have reached the organization limit of your plan. Upgrade your subscription to create more organizations.",
+                ),
+            )),
+            Some(OrganizationServiceError::OwnerNotFound) => Err(ApiError::NotFound(
+                messages.get_user_message("fetch.not_found", "User not found."),
+            )),
+            Some(OrganizationServiceError::LimitsExceeded) => Err(limits_exceeded(&messages)),
+            Some(OrganizationServiceError::RequestInProgress) => Err(ApiError::Conflict(
+                messages.get_organization_message(
+                    "create.in_progress",
+                    "A request with this idempotency key is still being processed.",
+                ),
+            )),
+            _ => {
+                error!("❌ Failed to create organization: {:?}", err);
+                Err(ApiError::Internal(messages.get_organization_message(
+                    "create.failed",
+                    "Failed to create organization.",
+                )))
+            }
+        },
     }
 }
 
 pub async fn get_organization_handler(
+    req: HttpRequest,
     organization_service: web::Data<Arc<OrganizationService>>,
     org_id: web::Path<String>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
     match organization_service.get_organization_by_id(&org_id).await {
-        Ok(Some(organization)) => HttpResponse::Ok().json(organization),
-        Ok(None) => HttpResponse::NotFound().body("Organization not found"),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Some(organization)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages
+                .get_organization_message("fetch.success", "Organization fetched successfully."),
+            organization,
+        ))),
+        Ok(None) => Err(ApiError::NotFound(
+            messages.get_organization_message("fetch.not_found", "Organization not found."),
+        )),
+        Err(err) if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+        Err(err) => {
+            error!("❌ Failed to fetch organization: {:?}", err);
+            Err(ApiError::Internal(messages.get_organization_message(
+                "fetch.failed",
+                "Failed to fetch organization.",
+            )))
+        }
     }
 }
 
 pub async fn get_all_organizations_handler(
+    req: HttpRequest,
     organization_service: web::Data<Arc<OrganizationService>>,
-) -> impl Responder {
-    match organization_service.get_all_organizations().await {
-        Ok(orgs) => HttpResponse::Ok().json(orgs),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    pagination: web::Query<PaginationQuery>,
+    search: web::Query<OrganizationSearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let filter = organization_filter(&search, &messages)?;
+
+    match organization_service
+        .get_all_organizations(filter, &pagination)
+        .await
+    {
+        Ok(orgs) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_organization_message(
+                "fetch.all_success",
+                "All organizations fetched successfully.",
+            ),
+            orgs,
+        ))),
+        Err(err) => {
+            error!("❌ Failed to fetch organizations: {:?}", err);
+            Err(ApiError::Internal(messages.get_organization_message(
+                "fetch.all_failed",
+                "Failed to fetch organizations.",
+            )))
+        }
+    }
+}
+
+fn organization_filter(
+    search: &OrganizationSearchQuery,
+    messages: &Messages,
+) -> Result<Document, ApiError> {
+    search.filter_document().map_err(|(param, value)| {
+        ApiError::bad_request(
+            messages.get_organization_message("fetch.invalid_filter", "Unsupported filter value."),
+            Some(json!({ param: value })),
+        )
+    })
+}
+
+pub async fn count_organizations_handler(
+    req: HttpRequest,
+    organization_service: web::Data<Arc<OrganizationService>>,
+    search: web::Query<OrganizationSearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let filter = organization_filter(&search, &messages)?;
+
+    match organization_service.count_organizations(filter).await {
+        Ok(count) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_organization_message(
+                "fetch.count_success",
+                "Organizations counted successfully.",
+            ),
+            CountResponse::from(count),
+        ))),
+        Err(err) => {
+            error!("❌ Failed to count organizations: {:?}", err);
+            Err(ApiError::Internal(messages.get_organization_message(
+                "fetch.count_failed",
+                "Failed to count organizations.",
+            )))
+        }
     }
 }
 
 pub async fn update_organization_handler(
+    req: HttpRequest,
     organization_service: web::Data<Arc<OrganizationService>>,
+    auth_user: AuthenticatedUser,
     org_id: web::Path<String>,
-    organization: web::Json<Organization>,
-) -> impl Responder {
+    organization: web::Json<UpdateOrganizationRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = organization.into_inner();
+
+    if let Some(field) = data.immutable_field() {
+        return Err(ApiError::bad_request(
+            messages.get_organization_message(
+                "update.immutable_field",
+                "One or more fields in the request cannot be updated.",
+            ),
+            Some(json!({ "field": field })),
+        ));
+    }
+
+    validate_organization_update(&data, &messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_organization_message("invalid_data", "Invalid organization data."),
+        )
+    })?;
+
     match organization_service
-        .update_organization(&org_id, organization.into_inner())
+        .update_organization(&org_id, data, &auth_user.claims.email)
         .await
     {
-        Ok(updated_org) => HttpResponse::Ok().json(updated_org),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(updated_org) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages
+                .get_organization_message("update.success", "Organization updated successfully."),
+            updated_org,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(OrganizationServiceError::Forbidden) => {
+                Err(ApiError::Forbidden(messages.get_organization_message(
+                    "update.forbidden",
+                    "Only the owner or an admin can change this organization.",
+                )))
+            }
+            Some(OrganizationServiceError::LimitsExceeded) => Err(limits_exceeded(&messages)),
+            Some(OrganizationServiceError::VersionConflict) => {
+                Err(ApiError::Conflict(messages.get_organization_message(
+                    "update.conflict",
+                    "The organization was changed by someone else. Reload and try again.",
+                )))
+            }
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to update organization: {:?}", err);
+                Err(ApiError::Internal(messages.get_organization_message(
+                    "update.failed",
+                    "Failed to update organization.",
+                )))
+            }
+        },
     }
 }
 
+fn invalid_id(messages: &Messages) -> ApiError {
+    ApiError::bad_request(
+        messages.get_organization_message("id.invalid", "Invalid organization id."),
+        None,
+    )
+}
+
+fn limits_exceeded(messages: &Messages) -> ApiError {
+    ApiError::bad_request(
+        messages.get_organization_message(
+            "limits_exceeded",
+            "The requested organization limits exceed your subscription plan.",
+        ),
+        None,
+    )
+}
+
 pub async fn delete_organization_handler(
+    req: HttpRequest,
+    organization_service: web::Data<Arc<OrganizationService>>,
+    auth_user: AuthenticatedUser,
+    org_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_service
+        .delete_organization(&org_id, &auth_user.claims.email)
+        .await
+    {
+        Ok(_) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages
+                .get_organization_message("delete.success", "Organization deleted successfully."),
+            None::<()>,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(OrganizationServiceError::Forbidden) => {
+                Err(ApiError::Forbidden(messages.get_organization_message(
+                    "delete.forbidden",
+                    "Only the owner can delete this organization.",
+                )))
+            }
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to delete organization: {:?}", err);
+                Err(ApiError::Internal(messages.get_organization_message(
+                    "delete.failed",
+                    "Failed to delete organization.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn transfer_ownership_handler(
+    req: HttpRequest,
     organization_service: web::Data<Arc<OrganizationService>>,
+    auth_user: AuthenticatedUser,
     org_id: web::Path<String>,
-) -> impl Responder {
-    match organization_service.delete_organization(&org_id).await {
-        Ok(_) => HttpResponse::Ok().body("Organization deleted successfully"),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+    payload: web::Json<TransferOwnershipRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_service
+        .transfer_ownership(&org_id, &payload.new_owner_id, &auth_user.claims.email)
+        .await
+    {
+        Ok(organization) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_organization_message(
+                "transfer.success",
+                "Organization ownership transferred successfully.",
+            ),
+            organization,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(OrganizationServiceError::Forbidden) => {
+                Err(ApiError::Forbidden(messages.get_organization_message(
+                    "transfer.forbidden",
+                    "Only the owner can transfer this organization.",
+                )))
+            }
+            Some(OrganizationServiceError::NewOwnerNotFound) => Err(ApiError::NotFound(
+                messages.get_user_message("fetch.not_found", "User not found."),
+            )),
+            Some(OrganizationServiceError::NewOwnerNotMember) => Err(ApiError::bad_request(
+                messages.get_organization_message(
+                    "transfer.not_member",
+                    "The new owner must be a member of the organization.",
+                ),
+                None,
+            )),
+            Some(OrganizationServiceError::OrganizationLimitReached) => {
+                Err(ApiError::Conflict(messages.get_organization_message(
+                    "transfer.organization_limit_reached",
+                    "The new owner has reached the organization limit of their plan.",
+                )))
+            }
+            Some(OrganizationServiceError::LimitsExceeded) => Err(limits_exceeded(&messages)),
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to transfer organization ownership: {:?}", err);
+                Err(ApiError::Internal(messages.get_organization_message(
+                    "transfer.failed",
+                    "Failed to transfer organization ownership.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn get_organization_audit_handler(
+    req: HttpRequest,
+    organization_service: web::Data<Arc<OrganizationService>>,
+    auth_user: AuthenticatedUser,
+    org_id: web::Path<String>,
+    pagination: web::Query<PaginationQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_service
+        .get_audit_logs(&org_id, &auth_user.claims.email, &pagination)
+        .await
+    {
+        Ok(logs) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_organization_message("audit.success", "Audit log fetched successfully."),
+            logs,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OrganizationNotFound) => Err(ApiError::NotFound(
+                messages.get_organization_message("fetch.not_found", "Organization not found."),
+            )),
+            Some(OrganizationServiceError::Forbidden) => {
+                Err(ApiError::Forbidden(messages.get_organization_message(
+                    "audit.forbidden",
+                    "You are not allowed to view the audit log of this organization.",
+                )))
+            }
+            _ if err.is::<oid::Error>() => Err(invalid_id(&messages)),
+            _ => {
+                error!("❌ Failed to fetch audit log: {:?}", err);
+                Err(ApiError::Internal(messages.get_organization_message(
+                    "audit.failed",
+                    "Failed to fetch audit log.",
+                )))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::lang::Lang;
+    use actix_web::{
+        ResponseError,
+        body::to_bytes,
+        http::{StatusCode, header},
+    };
+
+    async fn render(err: ApiError) -> (StatusCode, String, serde_json::Value) {
+        let response = err.error_response();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, content_type, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn organization_errors_are_rendered_as_the_json_envelope() {
+        let messages = Messages::new(Lang::En);
+
+        let (status, content_type, body) = render(invalid_id(&messages)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(content_type, "application/json");
+        assert_eq!(body["message"], "Invalid organization id.");
+        assert!(body.get("data").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn internal_errors_keep_the_envelope_instead_of_a_plain_body() {
+        let (status, content_type, body) = render(ApiError::Internal("Failed.".to_string())).await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(content_type, "application/json");
+        assert_eq!(body, json!({ "message": "Failed." }));
     }
 }
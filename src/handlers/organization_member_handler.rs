@@ -0,0 +1,298 @@
+use actix_web::{HttpRequest, HttpResponse, http::StatusCode, web};
+use bson::oid;
+use log::error;
+use std::sync::Arc;
+
+use crate::{
+    config::json::payload_too_large,
+    services::organization_member_service::{
+        OrganizationMemberService, OrganizationMemberServiceError,
+    },
+    types::{
+        auth::authenticated_user::AuthenticatedUser,
+        requests::member::{
+            invite_member_request::InviteMemberRequest,
+            update_member_role_request::UpdateMemberRoleRequest,
+        },
+        responses::{api_error::ApiError, api_response::ApiResponse},
+    },
+    utils::{
+        locale_utils::{Messages, get_lang},
+        validation_utils::{validate_fields, validate_member_identifiers},
+    },
+    validations::name::validate_name,
+};
+
+pub async fn import_members_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    org_id: web::Path<String>,
+    body: Result<String, actix_web::Error>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let body = body.map_err(|err| {
+        if err.as_response_error().status_code() == StatusCode::PAYLOAD_TOO_LARGE {
+            payload_too_large(&messages)
+        } else {
+            ApiError::bad_request(
+                messages
+                    .get_validation_message("body.invalid", "The request body could not be read."),
+                Some(err.to_string().into()),
+            )
+        }
+    })?;
+
+    match organization_member_service
+        .import_members(&org_id, &user.claims.email, &body, &messages)
+        .await
+    {
+        Ok(report) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_member_message("import.success", "Member import processed."),
+            report,
+        ))),
+        Err(err) => Err(member_error(
+            err,
+            &messages,
+            messages.get_member_message("import.failed", "Failed to import members."),
+        )),
+    }
+}
+
+pub async fn get_members_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    org_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_member_service
+        .get_members(&org_id, &user.claims.email)
+        .await
+    {
+        Ok(members) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_member_message("fetch.success", "Members retrieved successfully."),
+            members,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationMemberServiceError>() {
+            Some(OrganizationMemberServiceError::Forbidden) => {
+                Err(ApiError::Forbidden(messages.get_member_message(
+                    "fetch.forbidden",
+                    "Only members of this organization can see its members.",
+                )))
+            }
+            _ => Err(member_error(
+                err,
+                &messages,
+                messages.get_member_message("fetch.failed", "Failed to retrieve members."),
+            )),
+        },
+    }
+}
+
+pub async fn invite_member_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    org_id: web::Path<String>,
+    payload: web::Json<InviteMemberRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let data = payload.into_inner();
+
+    validate_fields(vec![("name", &data.name, validate_name)], &messages)
+        .and_then(|_| validate_member_identifiers(&data.identifiers, &messages))
+        .map_err(|errs| {
+            ApiError::validation(
+                errs,
+                messages.get_member_message("invite.invalid_data", "Invalid member data."),
+            )
+        })?;
+
+    match organization_member_service
+        .invite_member(&org_id, &user.claims.email, data)
+        .await
+    {
+        Ok(member) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            messages.get_member_message("invite.success", "Member invited successfully."),
+            member,
+        ))),
+        Err(err) => Err(member_error(
+            err,
+            &messages,
+            messages.get_member_message("invite.failed", "Failed to invite member."),
+        )),
+    }
+}
+
+pub async fn update_member_role_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    path: web::Path<(String, String)>,
+    payload: web::Json<UpdateMemberRoleRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let (org_id, member_id) = path.into_inner();
+
+    match organization_member_service
+        .update_member_role(
+            &org_id,
+            &member_id,
+            &user.claims.email,
+            payload.into_inner().role,
+        )
+        .await
+    {
+        Ok(member) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_member_message("role.success", "Member role updated successfully."),
+            member,
+        ))),
+        Err(err) => Err(member_error(
+            err,
+            &messages,
+            messages.get_member_message("role.failed", "Failed to update member role."),
+        )),
+    }
+}
+
+pub async fn join_organization_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    org_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_member_service
+        .join_organization(&org_id, &user.claims.email)
+        .await
+    {
+        Ok(member) => Ok(HttpResponse::Created().json(ApiResponse::success(
+            messages.get_member_message("join.success", "Joined organization successfully."),
+            member,
+        ))),
+        Err(err) => Err(member_error(
+            err,
+            &messages,
+            messages.get_member_message("join.failed", "Failed to join organization."),
+        )),
+    }
+}
+
+pub async fn leave_organization_handler(
+    req: HttpRequest,
+    user: AuthenticatedUser,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
+    org_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    match organization_member_service
+        .leave_organization(&org_id, &user.claims.email)
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_member_message("leave.success", "Left organization successfully."),
+            None::<()>,
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationMemberServiceError>() {
+            Some(OrganizationMemberServiceError::LastAdmin) => {
+                Err(ApiError::Conflict(messages.get_member_message(
+                    "leave.last_admin",
+                    "The last admin of an organization cannot leave it.",
+                )))
+            }
+            _ => Err(member_error(
+                err,
+                &messages,
+                messages.get_member_message("leave.failed", "Failed to leave organization."),
+            )),
+        },
+    }
+}
+
+fn member_error(err: anyhow::Error, messages: &Messages, failure: String) -> ApiError {
+    match err.downcast_ref::<OrganizationMemberServiceError>() {
+        Some(OrganizationMemberServiceError::OrganizationNotFound) => ApiError::NotFound(
+            messages.get_member_message("import.not_found", "Organization not found."),
+        ),
+        Some(OrganizationMemberServiceError::Forbidden) => {
+            ApiError::Forbidden(messages.get_member_message(
+                "invite.forbidden",
+                "You are not allowed to manage members of this organization.",
+            ))
+        }
+        Some(OrganizationMemberServiceError::MemberLimitReached) => {
+            ApiError::Forbidden(messages.get_member_message(
+                "import.limit_reached",
+                "The organization member limit has been reached",
+            ))
+        }
+        Some(OrganizationMemberServiceError::MemberNotFound) => ApiError::NotFound(
+            messages.get_member_message("fetch.member_not_found", "Member not found."),
+        ),
+        Some(OrganizationMemberServiceError::LastAdmin) => {
+            ApiError::Conflict(messages.get_member_message(
+                "role.last_admin",
+                "The last admin of an organization cannot be demoted.",
+            ))
+        }
+        Some(OrganizationMemberServiceError::DuplicateIdentifier) => {
+            ApiError::Conflict(messages.get_member_message(
+                "invite.duplicate_identifier",
+                "Another member of this organization already uses that identifier.",
+            ))
+        }
+        Some(OrganizationMemberServiceError::AlreadyMember) => {
+            ApiError::Conflict(messages.get_member_message(
+                "join.already_member",
+                "You are already a member of this organization.",
+            ))
+        }
+        None if err.is::<oid::Error>() => ApiError::bad_request(
+            messages.get_organization_message("id.invalid", "Invalid organization id."),
+            None,
+        ),
+        None => {
+            error!("❌ Failed to manage organization member: {:?}", err);
+            ApiError::Internal(failure)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::lang::Lang;
+    use actix_web::ResponseError;
+    use bson::oid::ObjectId;
+
+    #[test]
+    fn a_malformed_organization_id_is_a_bad_request() {
+        let messages = Messages::new(Lang::En);
+        let err = anyhow::Error::from(ObjectId::parse_str("not-an-id").unwrap_err());
+
+        let api_error = member_error(err, &messages, "failed".to_string());
+
+        assert_eq!(api_error.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn unexpected_failures_stay_internal_errors() {
+        let messages = Messages::new(Lang::En);
+        let err = anyhow::anyhow!("connection reset");
+
+        let api_error = member_error(err, &messages, "failed".to_string());
+
+        assert_eq!(api_error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
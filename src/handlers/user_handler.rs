@@ -1,106 +1,705 @@
 use std::sync::Arc;
 
 use actix_web::{HttpRequest, HttpResponse, web};
+use bson::{Document, oid::ObjectId};
+use log::error;
+use serde_json::json;
 
 use crate::{
-    services::user_service::UserService,
+    services::{
+        organization_service::{OrganizationService, OrganizationServiceError},
+        user_service::{UserService, UserServiceError},
+    },
     types::{
-        requests::user::update_user_request::UpdateUserRequest,
-        responses::api_response::ApiResponse,
+        auth::{
+            admin_user::{AdminUser, is_admin_email},
+            authenticated_user::AuthenticatedUser,
+        },
+        requests::{
+            common::pagination_query::PaginationQuery,
+            user::{
+                dormant_users_query::DormantUsersQuery,
+                update_password_request::UpdatePasswordRequest,
+                update_subscription_request::UpdateSubscriptionRequest,
+                update_user_request::UpdateUserRequest,
+                update_user_status_request::UpdateUserStatusRequest,
+                user_search_query::UserSearchQuery,
+            },
+        },
+        responses::{
+            api_error::ApiError, api_response::ApiResponse, count_response::CountResponse,
+            user_response::UserResponse,
+        },
     },
     utils::{
+        auth_utils::normalize_email,
         locale_utils::{Messages, get_lang},
-        validation_utils::{handle_internal_error, handle_validation_error, validate_fields},
+        validation_utils::validate_fields,
     },
-    validations::email::validate_email,
+    validations::{email::validate_email, name::validate_name, password::validate_password},
 };
 
+fn validate_email_path(email: &str, messages: &Messages) -> Result<(), ApiError> {
+    validate_fields(vec![("email", email, validate_email)], messages).map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_auth_message("email.invalid", "Invalid email format."),
+        )
+    })
+}
+
+/// Accounts may only be changed by their owner or by an administrator.
+fn ensure_self_or_admin(
+    auth_user: &AuthenticatedUser,
+    email: &str,
+    messages: &Messages,
+) -> Result<(), ApiError> {
+    if auth_user.claims.email == normalize_email(email) || is_admin_email(&auth_user.claims.email) {
+        return Ok(());
+    }
+    Err(ApiError::Forbidden(messages.get_user_message(
+        "access.forbidden",
+        "You can only manage your own account.",
+    )))
+}
+
+fn parse_user_id(id: &str, messages: &Messages) -> Result<ObjectId, ApiError> {
+    ObjectId::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            messages.get_user_message("id.invalid", "Invalid user id."),
+            Some(json!({ "id": id })),
+        )
+    })
+}
+
 pub async fn get_all_users_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
-) -> HttpResponse {
+    pagination: web::Query<PaginationQuery>,
+    search: web::Query<UserSearchQuery>,
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
 
-    match user_service.get_all_users(&messages).await {
-        Ok(users) => HttpResponse::Ok().json(ApiResponse::success(
-            messages.get_user_message("fetch.all_success", "All users fetched successfully."),
-            users,
-        )),
-        Err(err) => handle_internal_error(err),
-    }
+    let sort = search.sort_document().map_err(|key| {
+        ApiError::bad_request(
+            messages.get_user_message("fetch.invalid_sort", "Unsupported sort key."),
+            Some(json!({ "sort": key })),
+        )
+    })?;
+
+    let filter = user_filter(&search, &messages)?;
+
+    let users = user_service
+        .get_all_users(filter, &pagination, sort)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_user_message("fetch.all_success", "All users fetched successfully."),
+        users.map(UserResponse::from),
+    )))
+}
+
+pub async fn count_users_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    search: web::Query<UserSearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let filter = user_filter(&search, &messages)?;
+
+    let count = user_service
+        .count_users(filter)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_user_message("fetch.count_success", "Users counted successfully."),
+        CountResponse::from(count),
+    )))
+}
+
+fn user_filter(search: &UserSearchQuery, messages: &Messages) -> Result<Document, ApiError> {
+    search.filter_document().map_err(|(param, value)| {
+        ApiError::bad_request(
+            messages.get_user_message("fetch.invalid_filter", "Unsupported filter value."),
+            Some(json!({ param: value })),
+        )
+    })
+}
+
+pub async fn get_dormant_users_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    _admin: AdminUser,
+    query: web::Query<DormantUsersQuery>,
+    pagination: web::Query<PaginationQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let users = user_service
+        .get_users_updated_before(query.before, &pagination)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_user_message("fetch.all_success", "All users fetched successfully."),
+        users.map(UserResponse::from),
+    )))
 }
 
 pub async fn get_user_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
     email: web::Path<String>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
 
-    if let Err(errs) = validate_fields(vec![("email", &email, validate_email)], &messages) {
-        let msg = messages.get_auth_message("email.invalid", "Invalid email format.");
-        return handle_validation_error(errs, &msg);
+    validate_email_path(&email, &messages)?;
+
+    match user_service.get_user(&email).await {
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("fetch.success", "User fetched successfully."),
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::NotFound(messages.get_user_message(
+            "fetch.not_found",
+            &format!("User not found: {}", &email),
+        ))),
+        Err(err) => Err(fetch_failed(err, &messages)),
     }
+}
+
+pub async fn me_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    auth_user: AuthenticatedUser,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
 
-    match user_service.get_user(&email, &messages).await {
-        Ok(Some(user)) => HttpResponse::Ok().json(ApiResponse::success(
+    match user_service.get_user(&auth_user.claims.email).await {
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
             messages.get_user_message("fetch.success", "User fetched successfully."),
-            user,
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::NotFound(
+            messages.get_user_message("fetch.not_found", "User not found."),
         )),
-        Ok(None) => HttpResponse::NotFound().json(ApiResponse::<()>::error(
-            messages.get_user_message("fetch.not_found", &format!("User not found: {}", &email)),
-            None,
-        )),
-        Err(err) => handle_internal_error(err),
+        Err(err) => Err(fetch_failed(err, &messages)),
     }
 }
 
+fn fetch_failed(err: UserServiceError, messages: &Messages) -> ApiError {
+    error!("❌ Failed to fetch user: {:?}", err);
+    ApiError::Internal(messages.get_user_message("fetch.failed", "Failed to fetch user."))
+}
+
 pub async fn update_user_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
+    auth_user: AuthenticatedUser,
     email: web::Path<String>,
     updated_user: web::Json<UpdateUserRequest>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let actor = Some(auth_user.claims.email.as_str());
+
+    validate_email_path(&email, &messages)?;
+    ensure_self_or_admin(&auth_user, &email, &messages)?;
+
+    let data = updated_user.into_inner();
+
+    validate_user_update(&data, &messages)?;
+
+    match user_service.update_user(&email, data, actor).await {
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("update.success", "User updated successfully."),
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::NotFound(messages.get_user_message(
+            "fetch.not_found",
+            &format!("User not found: {}", &email),
+        ))),
+        Err(err) => Err(err.to_api_error(&messages)),
+    }
+}
+
+/// Rejects fields that cannot be updated through a profile update and
+/// validates the new name with the same rules as registration.
+fn validate_user_update(data: &UpdateUserRequest, messages: &Messages) -> Result<(), ApiError> {
+    if let Some(field) = data.immutable_field() {
+        return Err(ApiError::bad_request(
+            messages.get_user_message(
+                "update.immutable_field",
+                "One or more fields in the request cannot be updated.",
+            ),
+            Some(json!({ "field": field })),
+        ));
+    }
+
+    if let Some(field) = data.unknown_field() {
+        return Err(ApiError::bad_request(
+            messages.get_user_message(
+                "update.unknown_field",
+                "The request contains a field that does not exist.",
+            ),
+            Some(json!({ "field": field })),
+        ));
+    }
+
+    if let Some(name) = &data.name {
+        validate_fields(vec![("name", name, validate_name)], messages).map_err(|errs| {
+            ApiError::validation(
+                errs,
+                messages.get_user_message("update.invalid_data", "Invalid user data."),
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_password_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    auth_user: AuthenticatedUser,
+    email: web::Path<String>,
+    payload: web::Json<UpdatePasswordRequest>,
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
+    let actor = Some(auth_user.claims.email.as_str());
+    let data = payload.into_inner();
 
-    if let Err(errs) = validate_fields(vec![("email", &email, validate_email)], &messages) {
-        let msg = messages.get_auth_message("email.invalid", "Invalid email format.");
-        return handle_validation_error(errs, &msg);
+    if auth_user.claims.email != normalize_email(&email) {
+        return Err(ApiError::Forbidden(messages.get_user_message(
+            "password.forbidden",
+            "You can only change your own password.",
+        )));
     }
 
+    validate_fields(
+        vec![
+            ("email", &email, validate_email),
+            ("new_password", &data.new_password, validate_password),
+        ],
+        &messages,
+    )
+    .map_err(|errs| {
+        ApiError::validation(
+            errs,
+            messages.get_user_message("password.invalid_data", "Invalid password data."),
+        )
+    })?;
+
+    user_service
+        .update_password(&email, &data.current_password, &data.new_password, actor)
+        .await
+        .map_err(|err| err.to_api_error(&messages))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::success(
+        messages.get_user_message("password.success", "Password updated successfully."),
+        None::<()>,
+    )))
+}
+
+pub async fn update_subscription_handler(
+    req: HttpRequest,
+    organization_service: web::Data<Arc<OrganizationService>>,
+    auth_user: AuthenticatedUser,
+    email: web::Path<String>,
+    payload: web::Json<UpdateSubscriptionRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let plan = payload.into_inner().plan;
+    let is_admin = is_admin_email(&auth_user.claims.email);
+
+    if !is_admin && auth_user.claims.email != normalize_email(&email) {
+        return Err(ApiError::Forbidden(messages.get_user_message(
+            "subscription.forbidden",
+            "You can only change your own subscription plan.",
+        )));
+    }
+
+    // Paid plans are granted by an operator once billing is settled.
+    if !is_admin && !plan.is_self_service() {
+        return Err(ApiError::Forbidden(messages.get_user_message(
+            "subscription.upgrade_requires_admin",
+            "Upgrading to a paid plan must be done by an administrator.",
+        )));
+    }
+
+    match organization_service
+        .change_subscription_plan(&email, plan, Some(&auth_user.claims.email))
+        .await
+    {
+        Ok(user) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("subscription.updated", "Subscription plan updated."),
+            UserResponse::from(user),
+        ))),
+        Err(err) => match err.downcast_ref::<OrganizationServiceError>() {
+            Some(OrganizationServiceError::OwnerNotFound) => Err(ApiError::NotFound(
+                messages.get_user_message("fetch.not_found", "User not found."),
+            )),
+            Some(OrganizationServiceError::PlanLimitsExceeded) => {
+                Err(ApiError::Conflict(messages.get_user_message(
+                    "subscription.downgrade_blocked",
+                    "Your organizations exceed the limits of the selected plan.",
+                )))
+            }
+            _ => {
+                error!("❌ Failed to update subscription plan: {:?}", err);
+                Err(ApiError::Internal(messages.get_user_message(
+                    "subscription.update_failed",
+                    "Failed to update subscription plan.",
+                )))
+            }
+        },
+    }
+}
+
+pub async fn update_user_status_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    AdminUser(admin): AdminUser,
+    email: web::Path<String>,
+    payload: web::Json<UpdateUserStatusRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    validate_email_path(&email, &messages)?;
+
     match user_service
-        .update_user(&email, updated_user.into_inner(), &messages)
+        .update_status(
+            &email,
+            payload.into_inner().status,
+            Some(&admin.claims.email),
+        )
         .await
     {
-        Ok(user) => HttpResponse::Ok().json(ApiResponse::success(
-            messages.get_user_message("update.success", "User updated successfully."),
-            user,
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("status.updated", "User status updated."),
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::NotFound(
+            messages.get_user_message("fetch.not_found", "User not found."),
         )),
-        Err(err) => handle_internal_error(err),
+        Err(err) => {
+            error!("❌ Failed to update user status: {:?}", err);
+            Err(ApiError::Internal(messages.get_user_message(
+                "status.update_failed",
+                "Failed to update user status.",
+            )))
+        }
     }
 }
 
 pub async fn delete_user_handler(
     req: HttpRequest,
     user_service: web::Data<Arc<UserService>>,
+    auth_user: AuthenticatedUser,
     email: web::Path<String>,
-) -> HttpResponse {
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+    let actor = Some(auth_user.claims.email.as_str());
+
+    validate_email_path(&email, &messages)?;
+    ensure_self_or_admin(&auth_user, &email, &messages)?;
+
+    match user_service.delete_user(&email, actor).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("delete.success", "User deleted successfully."),
+            None::<()>,
+        ))),
+        Err(err) => Err(err.to_api_error(&messages)),
+    }
+}
+
+pub async fn update_user_by_id_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    AdminUser(admin): AdminUser,
+    id: web::Path<String>,
+    updated_user: web::Json<UpdateUserRequest>,
+) -> Result<HttpResponse, ApiError> {
     let lang = get_lang(&req);
     let messages = Messages::new(lang);
+    let actor = Some(admin.claims.email.as_str());
 
-    if let Err(errs) = validate_fields(vec![("email", &email, validate_email)], &messages) {
-        let msg = messages.get_auth_message("email.invalid", "Invalid email format.");
-        return handle_validation_error(errs, &msg);
+    let user_id = parse_user_id(&id, &messages)?;
+    let data = updated_user.into_inner();
+
+    validate_user_update(&data, &messages)?;
+
+    match user_service.update_user_by_id(&user_id, data, actor).await {
+        Ok(Some(user)) => Ok(HttpResponse::Ok().json(ApiResponse::success(
+            messages.get_user_message("update.success", "User updated successfully."),
+            UserResponse::from(user),
+        ))),
+        Ok(None) => Err(ApiError::NotFound(messages.get_user_message(
+            "fetch.not_found",
+            &format!("User not found: {}", &id),
+        ))),
+        Err(err) => Err(err.to_api_error(&messages)),
     }
+}
 
-    match user_service.delete_user(&email, &messages).await {
-        Ok(_) => HttpResponse::Ok().json(ApiResponse::success(
+pub async fn delete_user_by_id_handler(
+    req: HttpRequest,
+    user_service: web::Data<Arc<UserService>>,
+    AdminUser(admin): AdminUser,
+    id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let lang = get_lang(&req);
+    let messages = Messages::new(lang);
+
+    let user_id = parse_user_id(&id, &messages)?;
+
+    match user_service
+        .delete_user_by_id(&user_id, Some(&admin.claims.email))
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::Ok().json(ApiResponse::success(
             messages.get_user_message("delete.success", "User deleted successfully."),
             None::<()>,
-        )),
-        Err(err) => handle_internal_error(err),
+        ))),
+        Err(err) => Err(err.to_api_error(&messages)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        repositories::in_memory_user_repository::{self, InMemoryUserRepository},
+        types::auth::claims::Claims,
+        utils::lang::Lang,
+    };
+    use actix_web::{ResponseError, body::to_bytes, http::StatusCode, test::TestRequest};
+
+    async fn get_user(repository: Arc<InMemoryUserRepository>, email: &str) -> HttpResponse {
+        let service = in_memory_user_repository::user_service(repository).await;
+        let result = get_user_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            web::Path::from(email.to_string()),
+        )
+        .await;
+        result.unwrap_or_else(|err| err.error_response())
+    }
+
+    #[actix_rt::test]
+    async fn get_user_returns_the_profile_without_the_password_hash() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+
+        let response = get_user(repository, "jane@example.com").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["email"], "jane@example.com");
+        assert!(body["data"].get("password").is_none());
+        assert!(body["data"].get("token_version").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn get_user_returns_404_for_an_unknown_email() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+
+        let response = get_user(repository, "nobody@example.com").await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn get_user_returns_500_when_the_lookup_fails() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        repository.fail_next(1);
+
+        let response = get_user(repository, "jane@example.com").await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    fn signed_in(email: &str) -> AuthenticatedUser {
+        AuthenticatedUser {
+            claims: Claims {
+                name: "Test User".to_string(),
+                email: email.to_string(),
+                exp: 0,
+                iat: 0,
+                iss: String::new(),
+                aud: String::new(),
+                ver: 0,
+            },
+        }
+    }
+
+    async fn delete_user(
+        repository: Arc<InMemoryUserRepository>,
+        auth_user: AuthenticatedUser,
+        email: &str,
+    ) -> HttpResponse {
+        let service = in_memory_user_repository::user_service(repository).await;
+        let result = delete_user_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            auth_user,
+            web::Path::from(email.to_string()),
+        )
+        .await;
+        result.unwrap_or_else(|err| err.error_response())
+    }
+
+    #[actix_rt::test]
+    async fn delete_user_rejects_another_account_with_403() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+
+        let response = delete_user(
+            repository.clone(),
+            signed_in("mallory@example.com"),
+            "jane@example.com",
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(repository.user("jane@example.com").is_some());
+    }
+
+    #[actix_rt::test]
+    async fn delete_user_allows_the_account_owner() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+
+        let response = delete_user(
+            repository.clone(),
+            signed_in("jane@example.com"),
+            "jane@example.com",
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(repository.user("jane@example.com").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn the_second_of_two_writers_with_the_same_version_gets_409() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+        let service = web::Data::new(Arc::new(
+            in_memory_user_repository::user_service(repository.clone()).await,
+        ));
+        let rename = |name: &str| UpdateUserRequest {
+            name: Some(name.to_string()),
+            version: Some(0),
+            extra: Default::default(),
+        };
+        let update = |body: UpdateUserRequest| {
+            update_user_handler(
+                TestRequest::default().to_http_request(),
+                service.clone(),
+                signed_in("jane@example.com"),
+                web::Path::from("jane@example.com".to_string()),
+                web::Json(body),
+            )
+        };
+
+        let first = update(rename("First Writer"))
+            .await
+            .unwrap_or_else(|err| err.error_response());
+        let second = update(rename("Second Writer"))
+            .await
+            .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::CONFLICT);
+        let stored = repository.user("jane@example.com").unwrap();
+        assert_eq!(stored.name, "First Writer");
+        assert_eq!(stored.version, 1);
+    }
+
+    #[actix_rt::test]
+    async fn update_user_rejects_an_invalid_name_and_unknown_fields() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+        let service = web::Data::new(Arc::new(
+            in_memory_user_repository::user_service(repository.clone()).await,
+        ));
+        let update = |body: serde_json::Value| {
+            update_user_handler(
+                TestRequest::default().to_http_request(),
+                service.clone(),
+                signed_in("jane@example.com"),
+                web::Path::from("jane@example.com".to_string()),
+                web::Json(serde_json::from_value(body).unwrap()),
+            )
+        };
+
+        let invalid_name = update(json!({ "name": "J4ne!" }))
+            .await
+            .unwrap_or_else(|err| err.error_response());
+        let unknown_field = update(json!({ "name": "Jane Roe", "nmae": "Jane Roe" }))
+            .await
+            .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(invalid_name.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(unknown_field.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(repository.user("jane@example.com").unwrap().version, 0);
+    }
+
+    #[actix_rt::test]
+    async fn update_password_rejects_another_account_with_403() {
+        let user = in_memory_user_repository::user("jane@example.com", "Securepassword123.");
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user]));
+        let service = in_memory_user_repository::user_service(repository.clone()).await;
+
+        let response = update_password_handler(
+            TestRequest::default().to_http_request(),
+            web::Data::new(Arc::new(service)),
+            signed_in("mallory@example.com"),
+            web::Path::from("jane@example.com".to_string()),
+            web::Json(UpdatePasswordRequest {
+                current_password: "Securepassword123.".to_string(),
+                new_password: "Str0ng!Passw0rd".to_string(),
+            }),
+        )
+        .await
+        .unwrap_or_else(|err| err.error_response());
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            repository.user("jane@example.com").unwrap().token_version,
+            0
+        );
+    }
+
+    #[test]
+    fn parse_user_id_accepts_an_object_id() {
+        let messages = Messages::new(Lang::En);
+
+        let id = parse_user_id("665f1f77bcf86cd799439021", &messages).unwrap();
+
+        assert_eq!(id.to_hex(), "665f1f77bcf86cd799439021");
+    }
+
+    #[test]
+    fn parse_user_id_rejects_a_malformed_id_with_400() {
+        let messages = Messages::new(Lang::En);
+
+        let err = parse_user_id("not-an-object-id", &messages).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
     }
 }
@@ -1,17 +1,40 @@
 use actix_web::web;
 use attendx_backend::{
     routes::{
-        auth_routes::configure_auth_routes, organization_routes::configure_organization_routes,
+        attendance_routes::configure_attendance_routes, auth_routes::configure_auth_routes,
+        excuse_routes::configure_excuse_routes, locale_routes::configure_locale_routes,
+        member_routes::configure_member_routes, organization_routes::configure_organization_routes,
         user_routes::configure_user_routes,
     },
-    setup::{database::setup_database, services::setup_services},
+    setup::{database::setup_database, server::run_server, services::setup_services},
 };
 use dotenv::dotenv;
 use log::info;
-use shuttle_actix_web::ShuttleActixWeb;
+use std::{net::SocketAddr, sync::Arc};
+
+struct AttendxService<F> {
+    config: F,
+    client: Arc<mongodb::Client>,
+}
+
+#[shuttle_runtime::async_trait]
+impl<F> shuttle_runtime::Service for AttendxService<F>
+where
+    F: FnOnce(&mut web::ServiceConfig) + Send + Clone + 'static,
+{
+    async fn bind(self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        run_server(self.config, addr, self.client)
+            .await
+            .map_err(shuttle_runtime::CustomError::new)?;
+        Ok(())
+    }
+}
 
 #[shuttle_runtime::main]
-async fn main() -> ShuttleActixWeb<impl FnOnce(&mut web::ServiceConfig) + Send + Clone + 'static> {
+async fn main() -> Result<
+    AttendxService<impl FnOnce(&mut web::ServiceConfig) + Send + Clone + 'static>,
+    shuttle_runtime::Error,
+> {
     dotenv().ok();
 
     if env_logger::try_init().is_err() {
@@ -21,17 +44,47 @@ async fn main() -> ShuttleActixWeb<impl FnOnce(&mut web::ServiceConfig) + Send +
     info!("🚀 Starting Smart Attendance Backend...");
 
     let client = setup_database().await;
-    let (user_service, organization_service) = setup_services(&client).await;
+    let (
+        user_service,
+        organization_service,
+        attendance_service,
+        organization_member_service,
+        password_reset_service,
+        excuse_service,
+    ) = setup_services(&client).await;
 
     let user_service_data = web::Data::new(user_service.clone());
     let organization_service_data = web::Data::new(organization_service.clone());
+    let attendance_service_data = web::Data::new(attendance_service.clone());
+    let organization_member_service_data = web::Data::new(organization_member_service.clone());
+    let password_reset_service_data = web::Data::new(password_reset_service.clone());
+    let excuse_service_data = web::Data::new(excuse_service.clone());
 
     let config = move |cfg: &mut web::ServiceConfig| {
-        configure_user_routes(cfg, user_service_data.clone());
-        configure_auth_routes(cfg, user_service_data.clone());
-        configure_organization_routes(cfg, organization_service_data.clone());
+        // Registered app-wide so `AuthenticatedUser` can check token versions in every scope.
+        cfg.app_data(user_service_data.clone());
+        configure_user_routes(
+            cfg,
+            user_service_data.clone(),
+            organization_service_data.clone(),
+        );
+        configure_attendance_routes(cfg, attendance_service_data.clone());
+        configure_auth_routes(
+            cfg,
+            user_service_data.clone(),
+            password_reset_service_data.clone(),
+        );
+        configure_excuse_routes(cfg, excuse_service_data.clone());
+        configure_locale_routes(cfg);
+        configure_member_routes(cfg, attendance_service_data.clone());
+        configure_organization_routes(
+            cfg,
+            organization_service_data.clone(),
+            attendance_service_data.clone(),
+            organization_member_service_data.clone(),
+        );
     };
 
     info!("✅ Application started successfully");
-    Ok(config.into())
+    Ok(AttendxService { config, client })
 }
@@ -15,6 +15,13 @@ pub struct Attendance {
     pub user_id: ObjectId,
     pub organization_id: ObjectId,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<ObjectId>,
+
+    /// UTC day of a class check-in; one check-in per member, class and day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_in_date: Option<DateTime<Utc>>,
+
     pub attendance_type: AttendanceType,
     pub status: AttendanceStatus,
 
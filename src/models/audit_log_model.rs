@@ -0,0 +1,61 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::models::audit::{audit_action::AuditAction, audit_target::AuditTarget};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditLog {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
+    #[serde(default)]
+    pub actor: Option<String>,
+
+    pub action: AuditAction,
+
+    pub target: AuditTarget,
+
+    pub target_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<ObjectId>,
+
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    pub fn new(
+        actor: Option<&str>,
+        action: AuditAction,
+        target: AuditTarget,
+        target_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            _id: None,
+            actor: actor.map(str::to_string),
+            action,
+            target,
+            target_id: target_id.into(),
+            organization_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn for_organization(
+        organization_id: ObjectId,
+        actor: Option<&str>,
+        action: AuditAction,
+    ) -> Self {
+        Self {
+            organization_id: Some(organization_id),
+            ..Self::new(
+                actor,
+                action,
+                AuditTarget::Organization,
+                organization_id.to_hex(),
+            )
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClassSession {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
+    pub organization_id: ObjectId,
+
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructor_id: Option<ObjectId>,
+
+    pub starts_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_in_opens_minutes_before: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_in_closes_minutes_after: Option<u32>,
+
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClassSession {
+    /// Earliest and latest accepted check-in, offset from the start on the
+    /// organization's wall clock. A missing bound leaves that side open.
+    pub fn check_in_window(&self, tz: Tz) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let local_start = self.starts_at.with_timezone(&tz).naive_local();
+        let offset = |minutes: i64| {
+            tz.from_local_datetime(&(local_start + Duration::minutes(minutes)))
+                .earliest()
+                .map(|at| at.with_timezone(&Utc))
+                .unwrap_or(self.starts_at + Duration::minutes(minutes))
+        };
+
+        (
+            self.check_in_opens_minutes_before
+                .map(|minutes| offset(-i64::from(minutes))),
+            self.check_in_closes_minutes_after
+                .map(|minutes| offset(i64::from(minutes))),
+        )
+    }
+}
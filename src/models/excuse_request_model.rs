@@ -0,0 +1,29 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::models::attendance::excuse_status::ExcuseStatus;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExcuseRequest {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
+    pub organization_id: ObjectId,
+
+    pub member_id: ObjectId,
+
+    pub date: NaiveDate,
+
+    pub reason: String,
+
+    pub status: ExcuseStatus,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<ObjectId>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,21 @@
+use bson::{DateTime, Document, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdempotencyKey {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
+    pub key: String,
+
+    pub scope: String,
+
+    pub user_id: ObjectId,
+
+    /// The body sent for the first request, stored once it succeeds so replays
+    /// keep working after the resource itself is gone. `None` while pending.
+    #[serde(default)]
+    pub response: Option<Document>,
+
+    pub created_at: DateTime,
+}
@@ -1,4 +1,9 @@
 pub mod attendance_model;
+pub mod audit_log_model;
+pub mod class_session_model;
+pub mod excuse_request_model;
+pub mod idempotency_key_model;
 pub mod organization_member_model;
 pub mod organization_model;
+pub mod password_reset_token_model;
 pub mod user_model;
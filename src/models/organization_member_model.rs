@@ -7,8 +7,14 @@ use crate::types::models::user::role::Role;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OrganizationMember {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
     pub organization_id: ObjectId,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<ObjectId>,
+
     pub name: String,
 
     pub role: Role,
@@ -1,8 +1,14 @@
 use bson::oid::ObjectId;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-use crate::types::models::organization::organization_limit::OrganizationLimits;
+use crate::{
+    types::models::organization::{
+        defaults::default_timezone, organization_limit::OrganizationLimits,
+    },
+    utils::{lang::Lang, locale_utils::Messages},
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Organization {
@@ -20,6 +26,20 @@ pub struct Organization {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo_url: Option<String>,
 
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    /// Language for content the server produces on the organization's behalf,
+    /// where there is no request to take `Accept-Language` from. Falls back to
+    /// the deployment's `DEFAULT_LANG` when unset.
+    #[serde(default = "Lang::deployment_default")]
+    pub default_lang: Lang,
+
+    /// Incremented on every update. Since this struct is also the update body,
+    /// a client that sends it back only overwrites the version it read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
 
@@ -27,4 +47,20 @@ pub struct Organization {
     pub updated_at: DateTime<Utc>,
 
     pub limits: OrganizationLimits,
+
+    /// Members currently in the organization. Joins and invites reserve a slot
+    /// with a conditional increment, so `limits.max_users` holds under
+    /// concurrency.
+    #[serde(default)]
+    pub member_count: u32,
+}
+
+impl Organization {
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    pub fn messages(&self) -> Messages {
+        Messages::new(self.default_lang)
+    }
 }
@@ -0,0 +1,16 @@
+use bson::{DateTime, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordResetToken {
+    #[serde(default)]
+    pub _id: Option<ObjectId>,
+
+    pub user_id: ObjectId,
+
+    pub token_hash: String,
+
+    pub expires_at: DateTime,
+
+    pub created_at: DateTime,
+}
@@ -16,6 +16,14 @@ pub struct User {
 
     pub email: String,
 
+    /// The address as typed at registration, kept only when its casing differs
+    /// from the case-folded `email`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_display: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
     pub password: String,
 
     #[serde(default)]
@@ -30,6 +38,23 @@ pub struct User {
     #[serde(default = "default_status")]
     pub status: UserStatus,
 
+    #[serde(default)]
+    pub email_verified: bool,
+
+    #[serde(default)]
+    pub token_version: u32,
+
+    #[serde(default)]
+    pub failed_login_attempts: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_until: Option<DateTime<Utc>>,
+
+    /// Incremented on every profile update; clients send it back to detect
+    /// concurrent edits. Documents written before versioning read as 0.
+    #[serde(default)]
+    pub version: u32,
+
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
 
@@ -0,0 +1,187 @@
+use crate::constants::ATTENDANCE_COL_NAME;
+use crate::{
+    config::database::{duplicate_key_indexes, get_collection},
+    models::attendance_model::Attendance,
+    types::responses::attendance_summary::AttendanceSummary,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream::TryStreamExt;
+use mongodb::bson::{Document, doc, oid::ObjectId, to_bson};
+use mongodb::{Client, Collection, error::Result};
+use std::collections::HashSet;
+
+pub struct AttendanceRepository {
+    pub collection: Collection<Attendance>,
+}
+
+impl AttendanceRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, (*ATTENDANCE_COL_NAME).as_str()).await?;
+        Ok(Self { collection })
+    }
+
+    /// Inserts every record it can and returns the positions of those rejected
+    /// by a unique index.
+    pub async fn record_many(&self, records: &[Attendance]) -> Result<HashSet<usize>> {
+        if records.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        match self.collection.insert_many(records).ordered(false).await {
+            Ok(_) => Ok(HashSet::new()),
+            Err(err) => duplicate_key_indexes(&err).ok_or(err),
+        }
+    }
+
+    /// Members of the organization that already have a record between `start` and `end`.
+    pub async fn find_recorded_members(
+        &self,
+        organization_id: ObjectId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<HashSet<ObjectId>> {
+        let mut filter = doc! { "organization_id": organization_id };
+        filter.insert(
+            "created_at",
+            doc! { "$gte": to_bson(&start)?, "$lt": to_bson(&end)? },
+        );
+
+        let recorded = self.collection.distinct("user_id", filter).await?;
+        Ok(recorded
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
+
+    /// Members already checked in to the class on the given UTC day.
+    pub async fn find_checked_in_members(
+        &self,
+        class_id: ObjectId,
+        check_in_date: DateTime<Utc>,
+    ) -> Result<HashSet<ObjectId>> {
+        let filter = doc! { "class_id": class_id, "check_in_date": to_bson(&check_in_date)? };
+
+        let checked_in = self.collection.distinct("user_id", filter).await?;
+        Ok(checked_in
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
+
+    pub async fn summarize_by_member(
+        &self,
+        organization_id: ObjectId,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<AttendanceSummary>> {
+        let mut filter = doc! { "organization_id": organization_id };
+        insert_created_at_range(&mut filter, from, to)?;
+
+        let count_status = |status: &str| {
+            doc! { "$sum": { "$cond": [{ "$eq": ["$_id.status", status] }, "$count", 0] } }
+        };
+
+        let pipeline = vec![
+            doc! { "$match": filter },
+            doc! {
+                "$group": {
+                    "_id": { "user_id": "$user_id", "status": "$status" },
+                    "count": { "$sum": 1 },
+                }
+            },
+            doc! {
+                "$group": {
+                    "_id": "$_id.user_id",
+                    "present": count_status("Present"),
+                    "absent": count_status("Absent"),
+                    "late": count_status("Late"),
+                    "excused": count_status("Excused"),
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 0,
+                    "user_id": "$_id",
+                    "present": 1,
+                    "absent": 1,
+                    "late": 1,
+                    "excused": 1,
+                }
+            },
+            doc! { "$sort": { "user_id": 1 } },
+        ];
+
+        let cursor = self
+            .collection
+            .aggregate(pipeline)
+            .with_type::<AttendanceSummary>()
+            .await?;
+        let summaries: Vec<AttendanceSummary> = cursor.try_collect().await?;
+        Ok(summaries)
+    }
+
+    pub async fn summarize_member(
+        &self,
+        member_id: ObjectId,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<AttendanceSummary> {
+        let mut filter = doc! { "user_id": member_id };
+        insert_created_at_range(&mut filter, from, to)?;
+
+        let count_status = |status: &str| {
+            doc! { "$sum": { "$cond": [{ "$eq": ["$status", status] }, 1, 0] } }
+        };
+
+        let pipeline = vec![
+            doc! { "$match": filter },
+            doc! {
+                "$group": {
+                    "_id": "$user_id",
+                    "present": count_status("Present"),
+                    "absent": count_status("Absent"),
+                    "late": count_status("Late"),
+                    "excused": count_status("Excused"),
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 0,
+                    "user_id": "$_id",
+                    "present": 1,
+                    "absent": 1,
+                    "late": 1,
+                    "excused": 1,
+                }
+            },
+        ];
+
+        let mut cursor = self
+            .collection
+            .aggregate(pipeline)
+            .with_type::<AttendanceSummary>()
+            .await?;
+        Ok(cursor.try_next().await?.unwrap_or(AttendanceSummary {
+            user_id: member_id,
+            ..Default::default()
+        }))
+    }
+}
+
+fn insert_created_at_range(
+    filter: &mut Document,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let mut range = Document::new();
+    if let Some(from) = from {
+        range.insert("$gte", to_bson(&from)?);
+    }
+    if let Some(to) = to {
+        range.insert("$lte", to_bson(&to)?);
+    }
+    if !range.is_empty() {
+        filter.insert("created_at", range);
+    }
+    Ok(())
+}
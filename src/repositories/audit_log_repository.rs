@@ -0,0 +1,43 @@
+use crate::constants::AUDIT_LOGS_COL_NAME;
+use crate::{config::database::get_collection, models::audit_log_model::AuditLog};
+use futures_util::stream::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId};
+use mongodb::{Client, Collection, error::Result};
+
+pub struct AuditLogRepository {
+    pub collection: Collection<AuditLog>,
+}
+
+impl AuditLogRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, (*AUDIT_LOGS_COL_NAME).as_str()).await?;
+        Ok(Self { collection })
+    }
+
+    pub async fn insert_log(&self, log: &AuditLog) -> Result<()> {
+        self.collection.insert_one(log).await?;
+        Ok(())
+    }
+
+    pub async fn find_logs_by_organization(
+        &self,
+        organization_id: &ObjectId,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<AuditLog>> {
+        let cursor = self
+            .collection
+            .find(doc! { "organization_id": organization_id })
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .skip(skip)
+            .limit(limit as i64)
+            .await?;
+        cursor.try_collect().await
+    }
+
+    pub async fn count_logs_by_organization(&self, organization_id: &ObjectId) -> Result<u64> {
+        self.collection
+            .count_documents(doc! { "organization_id": organization_id })
+            .await
+    }
+}
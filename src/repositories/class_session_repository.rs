@@ -0,0 +1,36 @@
+use crate::constants::CLASS_COL_NAME;
+use crate::{config::database::get_collection, models::class_session_model::ClassSession};
+use mongodb::bson::{doc, oid::ObjectId};
+use mongodb::{Client, Collection, error::Result};
+
+pub struct ClassSessionRepository {
+    pub collection: Collection<ClassSession>,
+}
+
+impl ClassSessionRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, &CLASS_COL_NAME).await?;
+        Ok(Self { collection })
+    }
+
+    pub async fn create_class(&self, class_session: &ClassSession) -> Result<ClassSession> {
+        let result = self.collection.insert_one(class_session).await?;
+        let mut created = class_session.clone();
+        created._id = result.inserted_id.as_object_id();
+        Ok(created)
+    }
+
+    pub async fn find_class_by_id(&self, class_id: &ObjectId) -> Result<Option<ClassSession>> {
+        self.collection.find_one(doc! { "_id": class_id }).await
+    }
+
+    pub async fn find_class(
+        &self,
+        organization_id: &ObjectId,
+        class_id: &ObjectId,
+    ) -> Result<Option<ClassSession>> {
+        self.collection
+            .find_one(doc! { "_id": class_id, "organization_id": organization_id })
+            .await
+    }
+}
@@ -0,0 +1,142 @@
+use crate::constants::{ATTENDANCE_COL_NAME, EXCUSE_REQUESTS_COL_NAME};
+use crate::{
+    config::database::get_collection,
+    models::{attendance_model::Attendance, excuse_request_model::ExcuseRequest},
+    types::models::attendance::{
+        attendance_method::AttendanceMethod, attendance_status::AttendanceStatus,
+        attendance_type::AttendanceType, excuse_status::ExcuseStatus,
+    },
+};
+use chrono::{DateTime, Utc};
+use mongodb::bson::{doc, oid::ObjectId, to_bson};
+use mongodb::{Client, Collection, error::Result, options::ReturnDocument};
+
+pub struct ExcuseRequestRepository {
+    pub collection: Collection<ExcuseRequest>,
+    pub attendance_collection: Collection<Attendance>,
+}
+
+impl ExcuseRequestRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, &EXCUSE_REQUESTS_COL_NAME).await?;
+        let attendance_collection = get_collection(client, &ATTENDANCE_COL_NAME).await?;
+        Ok(Self {
+            collection,
+            attendance_collection,
+        })
+    }
+
+    pub async fn insert_request(&self, request: &ExcuseRequest) -> Result<ExcuseRequest> {
+        let result = self.collection.insert_one(request).await?;
+        Ok(ExcuseRequest {
+            _id: result.inserted_id.as_object_id(),
+            ..request.clone()
+        })
+    }
+
+    pub async fn find_request_by_id(&self, id: &ObjectId) -> Result<Option<ExcuseRequest>> {
+        self.collection.find_one(doc! { "_id": id }).await
+    }
+
+    /// Marks a pending request as rejected. Returns `None` when the request was
+    /// already processed.
+    pub async fn reject_request(
+        &self,
+        id: &ObjectId,
+        reviewer_id: &ObjectId,
+    ) -> Result<Option<ExcuseRequest>> {
+        self.collection
+            .find_one_and_update(
+                doc! { "_id": id, "status": to_bson(&ExcuseStatus::Pending)? },
+                doc! {
+                    "$set": {
+                        "status": to_bson(&ExcuseStatus::Rejected)?,
+                        "reviewed_by": reviewer_id,
+                        "reviewed_at": to_bson(&Utc::now())?,
+                    }
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    /// Marks a pending request as approved and upserts the member's attendance
+    /// for the excused day in the same transaction. Returns `None` when the
+    /// request was already processed.
+    pub async fn approve_request(
+        &self,
+        id: &ObjectId,
+        reviewer_id: &ObjectId,
+        day_start: DateTime<Utc>,
+        day_end: DateTime<Utc>,
+    ) -> Result<Option<ExcuseRequest>> {
+        let mut session = self.collection.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let result = async {
+            let now = to_bson(&Utc::now())?;
+            let Some(request) = self
+                .collection
+                .find_one_and_update(
+                    doc! { "_id": id, "status": to_bson(&ExcuseStatus::Pending)? },
+                    doc! {
+                        "$set": {
+                            "status": to_bson(&ExcuseStatus::Approved)?,
+                            "reviewed_by": reviewer_id,
+                            "reviewed_at": now.clone(),
+                        }
+                    },
+                )
+                .return_document(ReturnDocument::After)
+                .session(&mut session)
+                .await?
+            else {
+                return Ok(None);
+            };
+
+            self.attendance_collection
+                .update_one(
+                    doc! {
+                        "user_id": request.member_id,
+                        "organization_id": request.organization_id,
+                        "created_at": {
+                            "$gte": to_bson(&day_start)?,
+                            "$lt": to_bson(&day_end)?,
+                        },
+                    },
+                    doc! {
+                        "$set": {
+                            "status": to_bson(&AttendanceStatus::Excused)?,
+                            "updated_at": now,
+                        },
+                        "$setOnInsert": {
+                            "attendance_type": to_bson(&AttendanceType::SingleMark)?,
+                            "method": to_bson(&AttendanceMethod::Manual)?,
+                            "created_at": to_bson(&day_start)?,
+                        },
+                    },
+                )
+                .upsert(true)
+                .session(&mut session)
+                .await?;
+
+            Ok(Some(request))
+        }
+        .await;
+
+        match result {
+            Ok(Some(request)) => {
+                session.commit_transaction().await?;
+                Ok(Some(request))
+            }
+            Ok(None) => {
+                session.abort_transaction().await?;
+                Ok(None)
+            }
+            Err(err) => {
+                session.abort_transaction().await?;
+                Err(err)
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::constants::IDEMPOTENCY_KEYS_COL_NAME;
+use crate::{
+    config::database::{get_collection, is_duplicate_key_error},
+    models::idempotency_key_model::IdempotencyKey,
+};
+use mongodb::bson::{Document, doc, oid::ObjectId};
+use mongodb::{Client, Collection, error::Result};
+
+pub struct IdempotencyRepository {
+    pub collection: Collection<IdempotencyKey>,
+}
+
+impl IdempotencyRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, (*IDEMPOTENCY_KEYS_COL_NAME).as_str()).await?;
+        Ok(Self { collection })
+    }
+
+    pub async fn find_key(
+        &self,
+        user_id: &ObjectId,
+        scope: &str,
+        key: &str,
+    ) -> Result<Option<IdempotencyKey>> {
+        self.collection
+            .find_one(doc! { "user_id": user_id, "scope": scope, "key": key })
+            .await
+    }
+
+    /// Claims the key before any work is done. Returns `false` when another
+    /// request already holds it.
+    pub async fn reserve_key(&self, idempotency_key: &IdempotencyKey) -> Result<bool> {
+        match self.collection.insert_one(idempotency_key).await {
+            Ok(_) => Ok(true),
+            Err(err) if is_duplicate_key_error(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn complete_key(
+        &self,
+        user_id: &ObjectId,
+        scope: &str,
+        key: &str,
+        response: Document,
+    ) -> Result<()> {
+        self.collection
+            .update_one(
+                doc! { "user_id": user_id, "scope": scope, "key": key },
+                doc! { "$set": { "response": response } },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Frees a key whose request failed, so the client can retry with it.
+    pub async fn release_key(&self, user_id: &ObjectId, scope: &str, key: &str) -> Result<()> {
+        self.collection
+            .delete_one(doc! {
+                "user_id": user_id,
+                "scope": scope,
+                "key": key,
+                "response": null,
+            })
+            .await?;
+        Ok(())
+    }
+}
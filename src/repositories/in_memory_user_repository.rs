@@ -0,0 +1,452 @@
+//! A `UserRepositoryTrait` backed by a `Vec`, so services can be tested
+//! without a running MongoDB. Filters support top-level equality only, which
+//! covers every filter the services build for single-user lookups.
+
+use crate::{
+    models::{audit_log_model::AuditLog, user_model::User},
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        user_repository::{UserRepositoryTrait, version_filter},
+    },
+    services::{audit_service::AuditService, user_service::UserService},
+    types::{
+        models::user::{subscription::SubscriptionPlan, user_status::UserStatus},
+        requests::user::update_user_request::UpdateUserRequest,
+    },
+};
+use async_trait::async_trait;
+use bson::{Bson, Document, doc, oid::ObjectId, to_document};
+use chrono::{DateTime, Utc};
+use mongodb::{
+    Client,
+    error::{Error, ErrorKind, Result, WriteError, WriteFailure},
+};
+use std::{
+    io,
+    sync::{
+        Arc, Mutex, Once,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Mutex<Vec<User>>,
+    /// `(kind, value, user_id)` for organization members linked to an account.
+    member_identifiers: Mutex<Vec<(String, String, ObjectId)>>,
+    failures: AtomicU32,
+}
+
+impl InMemoryUserRepository {
+    pub fn with_users(users: Vec<User>) -> Self {
+        Self {
+            users: Mutex::new(users),
+            ..Default::default()
+        }
+    }
+
+    /// Links `user_id` to an organization member identified by `kind`
+    /// (such as `nim`) and `value`.
+    pub fn with_member_identifier(self, kind: &str, value: &str, user_id: ObjectId) -> Self {
+        self.member_identifiers.lock().unwrap().push((
+            kind.to_string(),
+            value.to_string(),
+            user_id,
+        ));
+        self
+    }
+
+    /// Makes the next `count` calls fail as if the connection dropped.
+    pub fn fail_next(&self, count: u32) {
+        self.failures.store(count, Ordering::SeqCst);
+    }
+
+    pub fn user(&self, email: &str) -> Option<User> {
+        self.users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.email == email)
+            .cloned()
+    }
+
+    fn check_failure(&self) -> Result<()> {
+        let injected = self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                left.checked_sub(1)
+            })
+            .is_ok();
+        if injected {
+            return Err(Error::from(io::ErrorKind::ConnectionReset));
+        }
+        Ok(())
+    }
+
+    /// Applies `change` to the first user matching `filter` and returns the
+    /// updated copy.
+    fn modify(&self, filter: &Document, change: impl FnOnce(&mut User)) -> Result<Option<User>> {
+        self.check_failure()?;
+        let mut users = self.users.lock().unwrap();
+        for user in users.iter_mut() {
+            if matches(user, filter)? {
+                change(user);
+                return Ok(Some(user.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn update_matching(
+        &self,
+        mut filter: Document,
+        update: UpdateUserRequest,
+    ) -> Result<Option<User>> {
+        if let Some(version) = update.version {
+            filter.insert("version", version_filter(version));
+        }
+        self.modify(&filter, |user| {
+            if let Some(name) = update.name {
+                user.name = name;
+            }
+            user.version += 1;
+            user.updated_at = Utc::now();
+        })
+    }
+}
+
+fn matches(user: &User, filter: &Document) -> Result<bool> {
+    let stored = to_document(user)?;
+    Ok(filter.iter().all(|(key, expected)| {
+        let actual = stored.get(key).unwrap_or(&Bson::Null);
+        match expected {
+            Bson::Document(operator) => match operator.get_array("$in") {
+                Ok(values) => values.iter().any(|value| same_value(actual, value)),
+                Err(_) => false,
+            },
+            value => same_value(actual, value),
+        }
+    }))
+}
+
+/// Mongo compares numbers by value, so an `Int32` filter matches an `Int64` field.
+fn same_value(actual: &Bson, expected: &Bson) -> bool {
+    match (as_integer(actual), as_integer(expected)) {
+        (Some(actual), Some(expected)) => actual == expected,
+        _ => actual == expected,
+    }
+}
+
+fn as_integer(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(value) => Some(i64::from(*value)),
+        Bson::Int64(value) => Some(*value),
+        _ => None,
+    }
+}
+
+pub fn duplicate_key_error() -> Error {
+    let write_error: WriteError =
+        bson::from_document(doc! { "code": 11000, "errmsg": "E11000 duplicate key" })
+            .expect("a write error document");
+    Error::from(ErrorKind::Write(WriteFailure::WriteError(write_error)))
+}
+
+#[async_trait]
+impl UserRepositoryTrait for InMemoryUserRepository {
+    async fn register_user(&self, user: &User) -> Result<User> {
+        self.check_failure()?;
+        let mut users = self.users.lock().unwrap();
+        if users.iter().any(|existing| existing.email == user.email) {
+            return Err(duplicate_key_error());
+        }
+        users.push(user.clone());
+        Ok(user.clone())
+    }
+
+    async fn find_one_by(&self, filter: Document) -> Result<Option<User>> {
+        self.check_failure()?;
+        let users = self.users.lock().unwrap();
+        for user in users.iter() {
+            if matches(user, &filter)? {
+                return Ok(Some(user.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn find_user(&self, field: &str, value: &str) -> Result<Option<User>> {
+        self.find_one_by(doc! { field: value }).await
+    }
+
+    async fn find_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>> {
+        self.find_one_by(doc! { "_id": user_id }).await
+    }
+
+    async fn find_users_by_member_identifier(&self, kind: &str, value: &str) -> Result<Vec<User>> {
+        self.check_failure()?;
+        let user_ids: Vec<ObjectId> = self
+            .member_identifiers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(k, v, _)| k == kind && v == value)
+            .map(|(_, _, user_id)| *user_id)
+            .collect();
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|user| user._id.is_some_and(|id| user_ids.contains(&id)))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_users(
+        &self,
+        filter: Document,
+        skip: u64,
+        limit: u64,
+        _sort: Document,
+    ) -> Result<Vec<User>> {
+        self.check_failure()?;
+        let users = self.users.lock().unwrap();
+        let mut found = Vec::new();
+        for user in users.iter() {
+            if matches(user, &filter)? {
+                found.push(user.clone());
+            }
+        }
+        Ok(found
+            .into_iter()
+            .skip(skip as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn count_users(&self, filter: Document) -> Result<u64> {
+        Ok(self
+            .find_users(filter, 0, u64::MAX, Document::new())
+            .await?
+            .len() as u64)
+    }
+
+    async fn find_users_updated_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<User>> {
+        self.check_failure()?;
+        let mut users: Vec<User> = self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|user| user.updated_at < cutoff)
+            .cloned()
+            .collect();
+        users.sort_by_key(|user| user.updated_at);
+        Ok(users
+            .into_iter()
+            .skip(skip as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn count_users_updated_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        self.check_failure()?;
+        let users = self.users.lock().unwrap();
+        Ok(users.iter().filter(|user| user.updated_at < cutoff).count() as u64)
+    }
+
+    async fn update_user(&self, email: &str, user: UpdateUserRequest) -> Result<Option<User>> {
+        self.update_matching(doc! { "email": email }, user)
+    }
+
+    async fn update_subscription_plan(
+        &self,
+        email: &str,
+        plan: &SubscriptionPlan,
+    ) -> Result<Option<User>> {
+        let filter = doc! {
+            "email": email,
+            "owned_organizations": { "$lte": plan.max_organizations() as i64 },
+        };
+        self.modify(&filter, |user| {
+            user.subscription_plan = plan.clone();
+            user.updated_at = Utc::now();
+        })
+    }
+
+    async fn update_status(&self, email: &str, status: &UserStatus) -> Result<Option<User>> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.status = status.clone();
+            user.updated_at = Utc::now();
+        })
+    }
+
+    async fn update_password(&self, email: &str, password_hash: &str) -> Result<()> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.password = password_hash.to_string();
+            user.token_version += 1;
+            user.updated_at = Utc::now();
+        })?;
+        Ok(())
+    }
+
+    async fn replace_password_hash(
+        &self,
+        email: &str,
+        current_hash: &str,
+        new_hash: &str,
+    ) -> Result<bool> {
+        let filter = doc! { "email": email, "password": current_hash };
+        let replaced = self.modify(&filter, |user| user.password = new_hash.to_string())?;
+        Ok(replaced.is_some())
+    }
+
+    async fn mark_email_verified(&self, email: &str) -> Result<Option<User>> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.email_verified = true;
+            user.updated_at = Utc::now();
+        })
+    }
+
+    async fn increment_token_version(&self, email: &str) -> Result<Option<User>> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.token_version += 1;
+            user.updated_at = Utc::now();
+        })
+    }
+
+    async fn record_failed_login(
+        &self,
+        email: &str,
+        threshold: u32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<()> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.failed_login_attempts += 1;
+            if user.failed_login_attempts >= threshold {
+                user.failed_login_attempts = 0;
+                user.locked_until = Some(locked_until);
+            }
+        })?;
+        Ok(())
+    }
+
+    async fn reset_failed_logins(&self, email: &str) -> Result<()> {
+        self.modify(&doc! { "email": email }, |user| {
+            user.failed_login_attempts = 0;
+            user.locked_until = None;
+        })?;
+        Ok(())
+    }
+
+    async fn reserve_organization_slot(
+        &self,
+        user_id: &ObjectId,
+        plan: &SubscriptionPlan,
+    ) -> Result<bool> {
+        self.check_failure()?;
+        let mut users = self.users.lock().unwrap();
+        Ok(users
+            .iter_mut()
+            .find(|user| user._id.as_ref() == Some(user_id))
+            .filter(|user| {
+                user.subscription_plan == *plan
+                    && user.owned_organizations < plan.max_organizations()
+            })
+            .map(|user| user.owned_organizations += 1)
+            .is_some())
+    }
+
+    async fn release_organization_slot(&self, user_id: &ObjectId) -> Result<()> {
+        self.modify(&doc! { "_id": user_id }, |user| {
+            user.owned_organizations = user.owned_organizations.saturating_sub(1);
+        })?;
+        Ok(())
+    }
+
+    async fn delete_user(&self, email: &str) -> Result<u64> {
+        self.check_failure()?;
+        let mut users = self.users.lock().unwrap();
+        let before = users.len();
+        users.retain(|user| user.email != email);
+        Ok((before - users.len()) as u64)
+    }
+
+    async fn update_user_by_id(
+        &self,
+        user_id: &ObjectId,
+        user: UpdateUserRequest,
+    ) -> Result<Option<User>> {
+        self.update_matching(doc! { "_id": user_id }, user)
+    }
+
+    async fn delete_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>> {
+        self.check_failure()?;
+        let mut users = self.users.lock().unwrap();
+        let position = users
+            .iter()
+            .position(|user| user._id.as_ref() == Some(user_id));
+        Ok(position.map(|index| users.remove(index)))
+    }
+}
+
+/// Sets the variables the JWT and cookie helpers read, once per test binary.
+pub fn init_test_env() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        // SAFETY: runs once, before any test reads these variables.
+        unsafe {
+            std::env::set_var("JWT_SECRET_KEY", "test-secret-key");
+            std::env::set_var("COOKIE_NAME", "attendx_test");
+        }
+    });
+}
+
+/// An audit service whose background writes go nowhere: the client never
+/// connects, so `record` only ever logs a failed insert.
+pub async fn detached_audit_service() -> Arc<AuditService> {
+    let client = Client::with_uri_str("mongodb://127.0.0.1:9")
+        .await
+        .expect("a lazily connecting client");
+    let repository = AuditLogRepository {
+        collection: client
+            .database("attendx_test")
+            .collection::<AuditLog>("audit_logs"),
+    };
+    Arc::new(AuditService::new(Arc::new(repository)))
+}
+
+pub async fn user_service(repository: Arc<InMemoryUserRepository>) -> UserService {
+    init_test_env();
+    UserService::new(repository, detached_audit_service().await, None)
+}
+
+/// A verified, active account whose password is `password`.
+pub fn user(email: &str, password: &str) -> User {
+    let now = Utc::now();
+    User {
+        _id: Some(ObjectId::new()),
+        name: "Test User".to_string(),
+        email: email.to_string(),
+        email_display: None,
+        username: None,
+        password: crate::utils::auth_utils::hash_password(password).expect("a password hash"),
+        organization_ids: Default::default(),
+        owned_organizations: 0,
+        subscription_plan: SubscriptionPlan::Free,
+        status: UserStatus::Active,
+        email_verified: true,
+        token_version: 0,
+        failed_login_attempts: 0,
+        locked_until: None,
+        version: 0,
+        created_at: now,
+        updated_at: now,
+    }
+}
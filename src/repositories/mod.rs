@@ -1,3 +1,11 @@
+pub mod attendance_repository;
+pub mod audit_log_repository;
+pub mod class_session_repository;
+pub mod excuse_request_repository;
+pub mod idempotency_repository;
+#[cfg(test)]
+pub mod in_memory_user_repository;
 pub mod organization_member_repository;
 pub mod organization_repository;
+pub mod password_reset_repository;
 pub mod user_repository;
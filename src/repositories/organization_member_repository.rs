@@ -1,17 +1,436 @@
-use crate::constants::USER_COL_NAME;
-use crate::{config::database::get_collection, models::user_model::User};
+use crate::constants::{
+    ATTENDANCE_COL_NAME, ORGANIZATION_MEMBERS_COL_NAME, ORGANIZATIONS_COL_NAME, USER_COL_NAME,
+};
+use crate::{
+    config::database::{duplicate_key_indexes, get_collection},
+    models::{organization_member_model::OrganizationMember, user_model::User},
+    types::{
+        models::user::role::Role, responses::member_attendance_status::MemberAttendanceStatus,
+    },
+};
+use chrono::{DateTime, Utc};
 use futures_util::stream::TryStreamExt;
-use mongodb::bson::{doc, oid::ObjectId, to_document};
-use mongodb::{Client, Collection, error::Result};
+use log::warn;
+use mongodb::bson::{Document, doc, oid::ObjectId, to_bson, to_document};
+use mongodb::{Client, ClientSession, Collection, error::Result, options::ReturnDocument};
+use std::collections::HashSet;
 
 pub struct OrganizationMemberRepository {
     pub collection: Collection<User>,
+    pub member_collection: Collection<OrganizationMember>,
+    organization_collection: Collection<Document>,
+}
+
+pub enum RoleChange {
+    Updated(OrganizationMember),
+    MemberNotFound,
+    LastAdmin,
+}
+
+/// Whether changing a member from `current` to `new` would leave the
+/// organization without an admin, given how many admins it has now.
+pub fn demotes_last_admin(current: &Role, new: &Role, admins: u64) -> bool {
+    *current == Role::Admin && *new != Role::Admin && admins <= 1
 }
 
 impl OrganizationMemberRepository {
     pub async fn new(client: &Client) -> Result<Self> {
         let collection = get_collection(client, &USER_COL_NAME).await?;
-        Ok(Self { collection })
+        let member_collection = get_collection(client, &ORGANIZATION_MEMBERS_COL_NAME).await?;
+        let organization_collection = get_collection(client, &ORGANIZATIONS_COL_NAME).await?;
+        Ok(Self {
+            collection,
+            member_collection,
+            organization_collection,
+        })
+    }
+
+    /// Inserts every member it can and returns the positions of those rejected
+    /// because an identifier is already taken in the organization. The
+    /// organization's `member_count` grows by the number inserted.
+    pub async fn insert_members(
+        &self,
+        organization_id: &ObjectId,
+        members: &[OrganizationMember],
+    ) -> Result<HashSet<usize>> {
+        if members.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rejected = match self
+            .member_collection
+            .insert_many(members)
+            .ordered(false)
+            .await
+        {
+            Ok(_) => HashSet::new(),
+            Err(err) => duplicate_key_indexes(&err).ok_or(err)?,
+        };
+
+        let inserted = (members.len() - rejected.len()) as i64;
+        if inserted > 0 {
+            self.organization_collection
+                .update_one(
+                    doc! { "_id": organization_id },
+                    doc! { "$inc": { "member_count": inserted } },
+                )
+                .await?;
+        }
+
+        Ok(rejected)
+    }
+
+    pub async fn count_members(&self, organization_id: &ObjectId) -> Result<u64> {
+        self.member_collection
+            .count_documents(doc! { "organization_id": organization_id })
+            .await
+    }
+
+    /// Each member with their latest check-in in `[start, end)`, restricted
+    /// to `class_id` when given. Members without one are `Absent`.
+    pub async fn find_members_with_attendance(
+        &self,
+        organization_id: &ObjectId,
+        class_id: Option<&ObjectId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<MemberAttendanceStatus>> {
+        let mut attendance_match = doc! {
+            "organization_id": organization_id,
+            "created_at": { "$gte": to_bson(&start)?, "$lt": to_bson(&end)? },
+            "$expr": { "$eq": ["$user_id", "$$member_id"] },
+        };
+        if let Some(class_id) = class_id {
+            attendance_match.insert("class_id", class_id);
+        }
+
+        let pipeline = vec![
+            doc! { "$match": { "organization_id": organization_id } },
+            doc! { "$sort": { "name": 1, "_id": 1 } },
+            doc! { "$skip": skip as i64 },
+            doc! { "$limit": limit as i64 },
+            doc! {
+                "$lookup": {
+                    "from": ATTENDANCE_COL_NAME.as_str(),
+                    "let": { "member_id": "$_id" },
+                    "pipeline": [
+                        { "$match": attendance_match },
+                        { "$sort": { "created_at": -1 } },
+                        { "$limit": 1 },
+                    ],
+                    "as": "attendance",
+                }
+            },
+            doc! {
+                "$project": {
+                    "_id": 0,
+                    "member_id": "$_id",
+                    "name": 1,
+                    "role": 1,
+                    "status": { "$ifNull": [{ "$first": "$attendance.status" }, "Absent"] },
+                    "clock_in": { "$first": "$attendance.clock_in" },
+                }
+            },
+        ];
+
+        let cursor = self
+            .member_collection
+            .aggregate(pipeline)
+            .with_type::<MemberAttendanceStatus>()
+            .await?;
+        let members: Vec<MemberAttendanceStatus> = cursor.try_collect().await?;
+        Ok(members)
+    }
+
+    pub async fn find_member(
+        &self,
+        organization_id: &ObjectId,
+        member_id: &ObjectId,
+    ) -> Result<Option<OrganizationMember>> {
+        self.member_collection
+            .find_one(doc! { "_id": member_id, "organization_id": organization_id })
+            .await
+    }
+
+    pub async fn find_member_by_id(
+        &self,
+        member_id: &ObjectId,
+    ) -> Result<Option<OrganizationMember>> {
+        self.member_collection
+            .find_one(doc! { "_id": member_id })
+            .await
+    }
+
+    pub async fn find_member_ids(
+        &self,
+        organization_id: &ObjectId,
+        member_ids: &[ObjectId],
+    ) -> Result<HashSet<ObjectId>> {
+        let found = self
+            .member_collection
+            .distinct(
+                "_id",
+                doc! { "organization_id": organization_id, "_id": { "$in": member_ids } },
+            )
+            .await?;
+        Ok(found
+            .into_iter()
+            .filter_map(|id| id.as_object_id())
+            .collect())
+    }
+
+    pub async fn find_member_by_user(
+        &self,
+        organization_id: &ObjectId,
+        user_id: &ObjectId,
+    ) -> Result<Option<OrganizationMember>> {
+        self.member_collection
+            .find_one(doc! { "organization_id": organization_id, "user_id": user_id })
+            .await
+    }
+
+    /// Whether the user is a member of the organization holding one of `roles`.
+    pub async fn has_role(
+        &self,
+        organization_id: &ObjectId,
+        user_id: &ObjectId,
+        roles: &[Role],
+    ) -> Result<bool> {
+        let roles = roles
+            .iter()
+            .map(to_bson)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let member = self
+            .member_collection
+            .find_one(doc! {
+                "organization_id": organization_id,
+                "user_id": user_id,
+                "role": { "$in": roles },
+            })
+            .await?;
+        Ok(member.is_some())
+    }
+
+    /// Adds the member unless the organization already has `max_members`,
+    /// linking it to its account when it has one. The slot is reserved inside
+    /// the transaction, so `None` means the limit was reached and nothing was
+    /// written.
+    pub async fn add_member(
+        &self,
+        member: &OrganizationMember,
+        max_members: u32,
+    ) -> Result<Option<OrganizationMember>> {
+        let mut session = self.member_collection.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let result = async {
+            if !self
+                .reserve_member_slot(&mut session, &member.organization_id, max_members)
+                .await?
+            {
+                return Ok(None);
+            }
+
+            let inserted = self
+                .member_collection
+                .insert_one(member)
+                .session(&mut session)
+                .await?;
+            if let Some(user_id) = member.user_id {
+                self.collection
+                    .update_one(
+                        doc! { "_id": user_id },
+                        doc! {
+                            "$addToSet": { "organization_ids": member.organization_id },
+                            "$set": { "updated_at": to_bson(&Utc::now())? },
+                        },
+                    )
+                    .session(&mut session)
+                    .await?;
+            }
+            Ok(Some(inserted.inserted_id.as_object_id()))
+        }
+        .await;
+
+        match result {
+            Ok(Some(member_id)) => {
+                session.commit_transaction().await?;
+                Ok(Some(OrganizationMember {
+                    _id: member_id,
+                    ..member.clone()
+                }))
+            }
+            Ok(None) => {
+                abort(&mut session).await;
+                Ok(None)
+            }
+            Err(err) => {
+                abort(&mut session).await;
+                Err(err)
+            }
+        }
+    }
+
+    pub async fn leave_organization(
+        &self,
+        organization_id: &ObjectId,
+        user_id: &ObjectId,
+    ) -> Result<bool> {
+        let mut session = self.member_collection.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let result = async {
+            let deleted = self
+                .member_collection
+                .delete_one(doc! { "organization_id": organization_id, "user_id": user_id })
+                .session(&mut session)
+                .await?;
+            if deleted.deleted_count == 0 {
+                return Ok(false);
+            }
+            self.organization_collection
+                .update_one(
+                    doc! { "_id": organization_id, "member_count": { "$gt": 0 } },
+                    doc! { "$inc": { "member_count": -1 } },
+                )
+                .session(&mut session)
+                .await?;
+            self.collection
+                .update_one(
+                    doc! { "_id": user_id },
+                    doc! {
+                        "$pull": { "organization_ids": organization_id },
+                        "$set": { "updated_at": to_bson(&Utc::now())? },
+                    },
+                )
+                .session(&mut session)
+                .await?;
+            Ok(true)
+        }
+        .await;
+
+        match result {
+            Ok(true) => {
+                session.commit_transaction().await?;
+                Ok(true)
+            }
+            Ok(false) => {
+                abort(&mut session).await;
+                Ok(false)
+            }
+            Err(err) => {
+                abort(&mut session).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Takes one of the organization's member slots. The increment only
+    /// matches while `member_count` is below `max_members`, so concurrent
+    /// joins and invites cannot push the organization past its limit.
+    async fn reserve_member_slot(
+        &self,
+        session: &mut ClientSession,
+        organization_id: &ObjectId,
+        max_members: u32,
+    ) -> Result<bool> {
+        let result = self
+            .organization_collection
+            .update_one(
+                doc! {
+                    "_id": organization_id,
+                    "member_count": { "$lt": i64::from(max_members) },
+                },
+                doc! { "$inc": { "member_count": 1 } },
+            )
+            .session(session)
+            .await?;
+        Ok(result.modified_count > 0)
+    }
+
+    pub async fn count_members_with_role(
+        &self,
+        organization_id: &ObjectId,
+        role: &Role,
+    ) -> Result<u64> {
+        self.member_collection
+            .count_documents(doc! {
+                "organization_id": organization_id,
+                "role": to_bson(role)?,
+            })
+            .await
+    }
+
+    /// Changes a member's role, refusing to demote the last admin. The check
+    /// and the write share a transaction that also touches the organization
+    /// document, so two concurrent demotions conflict instead of both passing.
+    pub async fn update_member_role(
+        &self,
+        organization_id: &ObjectId,
+        member_id: &ObjectId,
+        role: &Role,
+    ) -> Result<RoleChange> {
+        let mut session = self.member_collection.client().start_session().await?;
+        session.start_transaction().await?;
+
+        let result = async {
+            let filter = doc! { "_id": member_id, "organization_id": organization_id };
+            let Some(member) = self
+                .member_collection
+                .find_one(filter.clone())
+                .session(&mut session)
+                .await?
+            else {
+                return Ok(RoleChange::MemberNotFound);
+            };
+
+            if member.role == Role::Admin {
+                self.organization_collection
+                    .update_one(
+                        doc! { "_id": organization_id },
+                        doc! { "$set": { "updated_at": to_bson(&Utc::now())? } },
+                    )
+                    .session(&mut session)
+                    .await?;
+                let admins = self
+                    .member_collection
+                    .count_documents(doc! {
+                        "organization_id": organization_id,
+                        "role": to_bson(&Role::Admin)?,
+                    })
+                    .session(&mut session)
+                    .await?;
+                if demotes_last_admin(&member.role, role, admins) {
+                    return Ok(RoleChange::LastAdmin);
+                }
+            }
+
+            let updated = self
+                .member_collection
+                .find_one_and_update(filter, doc! { "$set": { "role": to_bson(role)? } })
+                .return_document(ReturnDocument::After)
+                .session(&mut session)
+                .await?;
+            Ok(updated.map_or(RoleChange::MemberNotFound, RoleChange::Updated))
+        }
+        .await;
+
+        match result {
+            Ok(RoleChange::Updated(member)) => {
+                session.commit_transaction().await?;
+                Ok(RoleChange::Updated(member))
+            }
+            Ok(rejected) => {
+                abort(&mut session).await;
+                Ok(rejected)
+            }
+            Err(err) => {
+                abort(&mut session).await;
+                Err(err)
+            }
+        }
     }
 
     pub async fn create_user(&self, user: &User) -> Result<User> {
@@ -19,10 +438,17 @@ impl OrganizationMemberRepository {
         Ok(User { ..user.clone() })
     }
 
-    pub async fn get_all_users(&self) -> Result<Vec<User>> {
-        let cursor = self.collection.find(doc! {}).await?;
-        let users: Vec<User> = cursor.try_collect().await?;
-        Ok(users)
+    pub async fn find_members_by_organization(
+        &self,
+        organization_id: &ObjectId,
+    ) -> Result<Vec<OrganizationMember>> {
+        let cursor = self
+            .member_collection
+            .find(doc! { "organization_id": organization_id })
+            .sort(doc! { "joined_at": 1 })
+            .await?;
+        let members: Vec<OrganizationMember> = cursor.try_collect().await?;
+        Ok(members)
     }
 
     pub async fn update_user(&self, user_id: &str, user: &User) -> Result<User> {
@@ -41,3 +467,29 @@ impl OrganizationMemberRepository {
         Ok(())
     }
 }
+
+/// Aborting is best effort: the server drops an unfinished transaction on
+/// its own, and the caller's error is the one worth reporting.
+async fn abort(session: &mut ClientSession) {
+    if let Err(err) = session.abort_transaction().await {
+        warn!("⚠️ Failed to abort transaction: {:?}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demoting_the_only_admin_is_refused() {
+        assert!(demotes_last_admin(&Role::Admin, &Role::Staff, 1));
+        assert!(demotes_last_admin(&Role::Admin, &Role::User, 0));
+    }
+
+    #[test]
+    fn other_role_changes_are_allowed() {
+        assert!(!demotes_last_admin(&Role::Admin, &Role::User, 2));
+        assert!(!demotes_last_admin(&Role::Admin, &Role::Admin, 1));
+        assert!(!demotes_last_admin(&Role::User, &Role::Staff, 1));
+    }
+}
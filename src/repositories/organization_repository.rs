@@ -1,8 +1,15 @@
 use crate::constants::ORGANIZATIONS_COL_NAME;
-use crate::{config::database::get_collection, models::organization_model::Organization};
+use crate::{
+    config::database::{get_collection, with_retry},
+    models::organization_model::Organization,
+    repositories::user_repository::version_filter,
+    types::requests::organization::update_organization_request::UpdateOrganizationRequest,
+};
+use chrono::Utc;
 use futures_util::stream::TryStreamExt;
-use mongodb::bson::{doc, oid::ObjectId, to_document};
-use mongodb::{Client, Collection, error::Result};
+use mongodb::bson::{Document, doc, oid::ObjectId, to_bson};
+use mongodb::{Client, Collection, error::Result, options::ReturnDocument};
+use std::future::IntoFuture;
 
 pub struct OrganizationRepository {
     collection: Collection<Organization>,
@@ -18,42 +25,169 @@ impl OrganizationRepository {
         &self,
         mut organization: Organization,
     ) -> Result<Organization> {
+        organization.version = Some(0);
         let insert_result = self.collection.insert_one(&organization).await?;
         organization._id = Some(insert_result.inserted_id.as_object_id().unwrap());
         Ok(organization)
     }
 
-    pub async fn find_organization_by_id(&self, org_id: &str) -> Result<Option<Organization>> {
-        let object_id = ObjectId::parse_str(org_id).unwrap();
-        self.collection.find_one(doc! { "_id": object_id }).await
+    pub async fn find_organization_by_id(
+        &self,
+        organization_id: &ObjectId,
+    ) -> Result<Option<Organization>> {
+        with_retry(|| {
+            self.collection
+                .find_one(doc! { "_id": organization_id })
+                .into_future()
+        })
+        .await
+    }
+
+    pub async fn find_organizations(
+        &self,
+        filter: Document,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<Organization>> {
+        with_retry(|| async {
+            let cursor = self
+                .collection
+                .find(filter.clone())
+                .sort(doc! { "_id": 1 })
+                .skip(skip)
+                .limit(limit as i64)
+                .await?;
+            cursor.try_collect().await
+        })
+        .await
     }
 
-    pub async fn get_all_organizations(&self) -> Result<Vec<Organization>> {
-        let cursor = self.collection.find(doc! {}).await?;
-        let organizations: Vec<Organization> = cursor.try_collect().await?;
-        Ok(organizations)
+    pub async fn find_organizations_by_owner(
+        &self,
+        owner_id: &ObjectId,
+    ) -> Result<Vec<Organization>> {
+        let cursor = self.collection.find(doc! { "owner_id": owner_id }).await?;
+        cursor.try_collect().await
+    }
+
+    pub async fn count_organizations(&self, filter: Document) -> Result<u64> {
+        self.collection.count_documents(filter).await
     }
 
+    /// Sets the fields present in `update`. `created_at` is kept as stored and
+    /// `updated_at` is always set to the current time. When the update carries
+    /// a `version`, it only matches that version.
     pub async fn update_organization(
         &self,
-        org_id: &str,
-        organization: &Organization,
-    ) -> Result<Organization> {
-        let object_id = ObjectId::parse_str(org_id).unwrap();
-        let update_doc = to_document(organization)?;
+        organization_id: &ObjectId,
+        update: &UpdateOrganizationRequest,
+    ) -> Result<Option<Organization>> {
+        let mut filter = doc! { "_id": organization_id };
+        if let Some(version) = update.version {
+            filter.insert("version", version_filter(version));
+        }
 
-        self.collection
-            .update_one(doc! { "_id": object_id }, doc! { "$set": update_doc })
-            .await?;
+        let update_doc = update_document(update)?;
 
-        Ok(organization.clone())
+        self.collection
+            .find_one_and_update(
+                filter,
+                doc! { "$set": update_doc, "$inc": { "version": 1 } },
+            )
+            .return_document(ReturnDocument::After)
+            .await
     }
 
-    pub async fn delete_organization(&self, org_id: &str) -> Result<()> {
-        let object_id = ObjectId::parse_str(org_id).unwrap();
+    /// Hands the organization to `new_owner_id`, but only while `current_owner_id`
+    /// still owns it, so two concurrent transfers cannot both succeed.
+    pub async fn transfer_ownership(
+        &self,
+        org_id: &ObjectId,
+        current_owner_id: &ObjectId,
+        new_owner_id: &ObjectId,
+    ) -> Result<Option<Organization>> {
         self.collection
-            .delete_one(doc! { "_id": object_id })
+            .find_one_and_update(
+                doc! { "_id": org_id, "owner_id": current_owner_id },
+                doc! {
+                    "$set": {
+                        "owner_id": new_owner_id,
+                        "updated_at": to_bson(&Utc::now())?,
+                    },
+                    "$inc": { "version": 1 },
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    /// Returns the number of deleted documents.
+    pub async fn delete_organization(&self, organization_id: &ObjectId) -> Result<u64> {
+        let result = self
+            .collection
+            .delete_one(doc! { "_id": organization_id })
             .await?;
-        Ok(())
+        Ok(result.deleted_count)
+    }
+}
+
+/// `created_at` is never part of the update; `updated_at` always is.
+fn update_document(update: &UpdateOrganizationRequest) -> Result<Document> {
+    let mut update_doc = Document::new();
+    if let Some(name) = &update.name {
+        update_doc.insert("name", name.trim());
+    }
+    if let Some(email) = &update.email {
+        update_doc.insert("email", email.trim());
+    }
+    if let Some(logo_url) = &update.logo_url {
+        update_doc.insert("logo_url", logo_url);
+    }
+    if let Some(timezone) = &update.timezone {
+        update_doc.insert("timezone", timezone);
+    }
+    if let Some(default_lang) = &update.default_lang {
+        update_doc.insert("default_lang", to_bson(default_lang)?);
+    }
+    if let Some(limits) = &update.limits {
+        update_doc.insert("limits", to_bson(limits)?);
+    }
+    update_doc.insert("updated_at", to_bson(&Utc::now())?);
+    Ok(update_doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use serde_json::json;
+
+    #[test]
+    fn updates_bump_updated_at_and_never_touch_protected_fields() {
+        let before = Utc::now();
+        let update: UpdateOrganizationRequest = serde_json::from_value(json!({
+            "name": "Renamed",
+            "owner_id": "665f1f77bcf86cd799439099",
+            "password": "hijacked",
+            "created_at": "2020-01-01T00:00:00Z",
+        }))
+        .unwrap();
+
+        let update_doc = update_document(&update).unwrap();
+
+        assert_eq!(update_doc.get_str("name").unwrap(), "Renamed");
+        for field in ["owner_id", "password", "created_at", "_id"] {
+            assert!(!update_doc.contains_key(field), "{field} must not be set");
+        }
+        let updated_at: DateTime<Utc> = update_doc.get_str("updated_at").unwrap().parse().unwrap();
+        assert!(updated_at >= before);
+    }
+
+    #[test]
+    fn protected_fields_are_reported_as_immutable() {
+        let update: UpdateOrganizationRequest =
+            serde_json::from_value(json!({ "owner_id": "665f1f77bcf86cd799439099" })).unwrap();
+
+        assert_eq!(update.immutable_field(), Some("owner_id"));
     }
 }
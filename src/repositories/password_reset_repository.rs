@@ -0,0 +1,57 @@
+use crate::constants::PASSWORD_RESET_TOKENS_COL_NAME;
+use crate::{
+    config::database::get_collection, models::password_reset_token_model::PasswordResetToken,
+};
+use async_trait::async_trait;
+use mongodb::bson::{DateTime, doc, oid::ObjectId};
+use mongodb::{Client, Collection, error::Result};
+
+pub struct PasswordResetRepository {
+    pub collection: Collection<PasswordResetToken>,
+}
+
+#[async_trait]
+pub trait PasswordResetRepositoryTrait: Send + Sync {
+    async fn insert_token(&self, token: &PasswordResetToken) -> Result<ObjectId>;
+
+    async fn find_active_token(&self, token_id: &ObjectId) -> Result<Option<PasswordResetToken>>;
+
+    /// Deletes the token and reports whether this call was the one that removed it,
+    /// so a token can only ever be redeemed once.
+    async fn consume_token(&self, token_id: &ObjectId) -> Result<bool>;
+
+    async fn delete_tokens_for_user(&self, user_id: &ObjectId) -> Result<()>;
+}
+
+impl PasswordResetRepository {
+    pub async fn new(client: &Client) -> Result<Self> {
+        let collection = get_collection(client, (*PASSWORD_RESET_TOKENS_COL_NAME).as_str()).await?;
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepositoryTrait for PasswordResetRepository {
+    async fn insert_token(&self, token: &PasswordResetToken) -> Result<ObjectId> {
+        let result = self.collection.insert_one(token).await?;
+        Ok(result.inserted_id.as_object_id().unwrap_or_default())
+    }
+
+    async fn find_active_token(&self, token_id: &ObjectId) -> Result<Option<PasswordResetToken>> {
+        self.collection
+            .find_one(doc! { "_id": token_id, "expires_at": { "$gt": DateTime::now() } })
+            .await
+    }
+
+    async fn consume_token(&self, token_id: &ObjectId) -> Result<bool> {
+        let result = self.collection.delete_one(doc! { "_id": token_id }).await?;
+        Ok(result.deleted_count > 0)
+    }
+
+    async fn delete_tokens_for_user(&self, user_id: &ObjectId) -> Result<()> {
+        self.collection
+            .delete_many(doc! { "user_id": user_id })
+            .await?;
+        Ok(())
+    }
+}
@@ -1,58 +1,445 @@
-use crate::constants::USER_COL_NAME;
+use crate::constants::{ORGANIZATION_MEMBERS_COL_NAME, USER_COL_NAME};
 use crate::{
-    config::database::get_collection, models::user_model::User,
-    types::requests::user::update_user_request::UpdateUserRequest,
+    config::database::{get_collection, with_retry},
+    models::{organization_member_model::OrganizationMember, user_model::User},
+    types::{
+        models::user::{subscription::SubscriptionPlan, user_status::UserStatus},
+        requests::user::update_user_request::UpdateUserRequest,
+    },
 };
-use bson::Document;
+use async_trait::async_trait;
+use bson::{Bson, Document};
+use chrono::{DateTime, Utc};
 use futures_util::stream::TryStreamExt;
-use mongodb::bson::{doc, to_document};
-use mongodb::{Client, Collection, error::Result};
+use mongodb::bson::{doc, oid::ObjectId, to_bson};
+use mongodb::{Client, Collection, error::Result, options::ReturnDocument};
+use std::future::IntoFuture;
 
 pub struct UserRepository {
     pub collection: Collection<User>,
+    member_collection: Collection<OrganizationMember>,
+}
+
+#[async_trait]
+pub trait UserRepositoryTrait: Send + Sync {
+    async fn register_user(&self, user: &User) -> Result<User>;
+
+    async fn find_one_by(&self, filter: Document) -> Result<Option<User>>;
+
+    async fn find_user(&self, field: &str, value: &str) -> Result<Option<User>>;
+
+    async fn find_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>>;
+
+    /// Accounts linked to an organization member whose `identifiers.<kind>`
+    /// equals `value`, across all organizations.
+    async fn find_users_by_member_identifier(&self, kind: &str, value: &str) -> Result<Vec<User>>;
+
+    async fn find_users(
+        &self,
+        filter: Document,
+        skip: u64,
+        limit: u64,
+        sort: Document,
+    ) -> Result<Vec<User>>;
+
+    async fn count_users(&self, filter: Document) -> Result<u64>;
+
+    /// Oldest first, so the accounts dormant the longest come first.
+    async fn find_users_updated_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<User>>;
+
+    async fn count_users_updated_before(&self, cutoff: DateTime<Utc>) -> Result<u64>;
+
+    async fn update_user(&self, email: &str, user: UpdateUserRequest) -> Result<Option<User>>;
+
+    /// Only applies when the user owns no more organizations than `plan`
+    /// allows, so a downgrade cannot race an organization being created.
+    async fn update_subscription_plan(
+        &self,
+        email: &str,
+        plan: &SubscriptionPlan,
+    ) -> Result<Option<User>>;
+
+    async fn update_status(&self, email: &str, status: &UserStatus) -> Result<Option<User>>;
+
+    /// Stores a new password hash and bumps the token version, so every
+    /// session issued under the old password stops working.
+    async fn update_password(&self, email: &str, password_hash: &str) -> Result<()>;
+
+    async fn replace_password_hash(
+        &self,
+        email: &str,
+        current_hash: &str,
+        new_hash: &str,
+    ) -> Result<bool>;
+
+    async fn mark_email_verified(&self, email: &str) -> Result<Option<User>>;
+
+    /// Bumps the user's token version, invalidating every JWT issued before it.
+    async fn increment_token_version(&self, email: &str) -> Result<Option<User>>;
+
+    /// Counts a failed login and, once `threshold` consecutive failures are
+    /// reached, locks the account until `locked_until` and starts counting again.
+    async fn record_failed_login(
+        &self,
+        email: &str,
+        threshold: u32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<()>;
+
+    async fn reset_failed_logins(&self, email: &str) -> Result<()>;
+
+    /// Takes a slot only while the user is still on `plan` and below its
+    /// organization limit.
+    async fn reserve_organization_slot(
+        &self,
+        user_id: &ObjectId,
+        plan: &SubscriptionPlan,
+    ) -> Result<bool>;
+
+    async fn release_organization_slot(&self, user_id: &ObjectId) -> Result<()>;
+
+    /// Returns the number of deleted documents.
+    async fn delete_user(&self, email: &str) -> Result<u64>;
+
+    async fn update_user_by_id(
+        &self,
+        user_id: &ObjectId,
+        user: UpdateUserRequest,
+    ) -> Result<Option<User>>;
+
+    async fn delete_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>>;
 }
 
 impl UserRepository {
     pub async fn new(client: &Client) -> Result<Self> {
         let collection = get_collection(client, (*USER_COL_NAME).as_str()).await?;
-        Ok(Self { collection })
+        let member_collection = get_collection(client, &ORGANIZATION_MEMBERS_COL_NAME).await?;
+        Ok(Self {
+            collection,
+            member_collection,
+        })
     }
 
-    pub async fn register_user(&self, user: &User) -> Result<User> {
+    async fn update_matching(
+        &self,
+        mut filter: Document,
+        user: UpdateUserRequest,
+    ) -> Result<Option<User>> {
+        if let Some(version) = user.version {
+            filter.insert("version", version_filter(version));
+        }
+
+        self.collection
+            .find_one_and_update(
+                filter,
+                doc! { "$set": profile_update_document(&user)?, "$inc": { "version": 1 } },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+}
+
+#[async_trait]
+impl UserRepositoryTrait for UserRepository {
+    async fn register_user(&self, user: &User) -> Result<User> {
         self.collection.insert_one(user).await?;
         Ok(User { ..user.clone() })
     }
 
-    pub async fn find_user(&self, field: &str, value: &str) -> Result<Option<User>> {
-        let mut filter = Document::new();
-        filter.insert(field, value);
-        self.collection.find_one(filter).await
+    async fn find_one_by(&self, filter: Document) -> Result<Option<User>> {
+        with_retry(|| self.collection.find_one(filter.clone()).into_future()).await
     }
 
-    pub async fn get_all_users(&self) -> Result<Vec<User>> {
-        let cursor = self.collection.find(doc! {}).await?;
-        let users: Vec<User> = cursor.try_collect().await?;
-        Ok(users)
+    async fn find_user(&self, field: &str, value: &str) -> Result<Option<User>> {
+        self.find_one_by(doc! { field: value }).await
     }
 
-    pub async fn update_user(
+    async fn find_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>> {
+        self.find_one_by(doc! { "_id": user_id }).await
+    }
+
+    async fn find_users_by_member_identifier(&self, kind: &str, value: &str) -> Result<Vec<User>> {
+        let field = format!("identifiers.{kind}");
+        let user_ids = self
+            .member_collection
+            .distinct(
+                "user_id",
+                doc! { &field: value, "user_id": { "$type": "objectId" } },
+            )
+            .await?;
+
+        let cursor = self
+            .collection
+            .find(doc! { "_id": { "$in": user_ids } })
+            .await?;
+        cursor.try_collect().await
+    }
+
+    async fn find_users(
+        &self,
+        filter: Document,
+        skip: u64,
+        limit: u64,
+        sort: Document,
+    ) -> Result<Vec<User>> {
+        with_retry(|| async {
+            let cursor = self
+                .collection
+                .find(filter.clone())
+                .sort(sort.clone())
+                .skip(skip)
+                .limit(limit as i64)
+                .await?;
+            cursor.try_collect().await
+        })
+        .await
+    }
+
+    async fn count_users(&self, filter: Document) -> Result<u64> {
+        self.collection.count_documents(filter).await
+    }
+
+    async fn find_users_updated_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        skip: u64,
+        limit: u64,
+    ) -> Result<Vec<User>> {
+        let filter = doc! { "updated_at": { "$lt": to_bson(&cutoff)? } };
+        self.find_users(filter, skip, limit, doc! { "updated_at": 1 })
+            .await
+    }
+
+    async fn count_users_updated_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let filter = doc! { "updated_at": { "$lt": to_bson(&cutoff)? } };
+        self.count_users(filter).await
+    }
+
+    async fn update_user(&self, email: &str, user: UpdateUserRequest) -> Result<Option<User>> {
+        self.update_matching(doc! { "email": email }, user).await
+    }
+
+    async fn update_subscription_plan(
         &self,
         email: &str,
-        user: UpdateUserRequest,
-    ) -> Result<UpdateUserRequest> {
+        plan: &SubscriptionPlan,
+    ) -> Result<Option<User>> {
+        self.collection
+            .find_one_and_update(
+                doc! {
+                    "email": email,
+                    "owned_organizations": { "$lte": plan.max_organizations() as i64 },
+                },
+                doc! {
+                    "$set": {
+                        "subscription_plan": to_bson(plan)?,
+                        "updated_at": to_bson(&Utc::now())?,
+                    }
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    async fn update_status(&self, email: &str, status: &UserStatus) -> Result<Option<User>> {
+        self.collection
+            .find_one_and_update(
+                doc! { "email": email },
+                doc! {
+                    "$set": {
+                        "status": to_bson(status)?,
+                        "updated_at": to_bson(&Utc::now())?,
+                    }
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    async fn update_password(&self, email: &str, password_hash: &str) -> Result<()> {
         let filter = doc! { "email": email };
-        let update_doc = to_document(&user)?;
+        let update = doc! {
+            "$set": {
+                "password": password_hash,
+                "updated_at": to_bson(&Utc::now())?,
+            },
+            "$inc": { "token_version": 1 },
+        };
+
+        self.collection.update_one(filter, update).await?;
+        Ok(())
+    }
+
+    async fn replace_password_hash(
+        &self,
+        email: &str,
+        current_hash: &str,
+        new_hash: &str,
+    ) -> Result<bool> {
+        let filter = doc! { "email": email, "password": current_hash };
+        let update = doc! { "$set": { "password": new_hash } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn mark_email_verified(&self, email: &str) -> Result<Option<User>> {
+        self.collection
+            .find_one_and_update(
+                doc! { "email": email },
+                doc! {
+                    "$set": {
+                        "email_verified": true,
+                        "updated_at": to_bson(&Utc::now())?,
+                    }
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    async fn increment_token_version(&self, email: &str) -> Result<Option<User>> {
+        self.collection
+            .find_one_and_update(
+                doc! { "email": email },
+                doc! {
+                    "$inc": { "token_version": 1 },
+                    "$set": { "updated_at": to_bson(&Utc::now())? },
+                },
+            )
+            .return_document(ReturnDocument::After)
+            .await
+    }
+
+    async fn record_failed_login(
+        &self,
+        email: &str,
+        threshold: u32,
+        locked_until: DateTime<Utc>,
+    ) -> Result<()> {
+        let attempts = doc! { "$add": [{ "$ifNull": ["$failed_login_attempts", 0] }, 1] };
+        let reached = doc! { "$gte": [attempts.clone(), i64::from(threshold)] };
+        let update = vec![doc! {
+            "$set": {
+                "failed_login_attempts": { "$cond": [reached.clone(), 0, attempts] },
+                "locked_until": { "$cond": [reached, to_bson(&locked_until)?, "$locked_until"] },
+            }
+        }];
 
         self.collection
-            .update_one(filter, doc! { "$set": update_doc })
+            .update_one(doc! { "email": email }, update)
             .await?;
+        Ok(())
+    }
+
+    async fn reset_failed_logins(&self, email: &str) -> Result<()> {
+        let filter = doc! {
+            "email": email,
+            "$or": [
+                { "failed_login_attempts": { "$gt": 0 } },
+                { "locked_until": { "$exists": true } },
+            ],
+        };
+        let update = doc! {
+            "$set": { "failed_login_attempts": 0 },
+            "$unset": { "locked_until": "" },
+        };
 
-        Ok(user)
+        self.collection.update_one(filter, update).await?;
+        Ok(())
     }
 
-    pub async fn delete_user(&self, email: &str) -> Result<()> {
-        let filter = doc! { "email": email };
-        self.collection.delete_one(filter).await?;
+    async fn reserve_organization_slot(
+        &self,
+        user_id: &ObjectId,
+        plan: &SubscriptionPlan,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "_id": user_id,
+            "subscription_plan": to_bson(plan)?,
+            "owned_organizations": { "$lt": plan.max_organizations() as i64 },
+        };
+        let update = doc! { "$inc": { "owned_organizations": 1 } };
+
+        let result = self.collection.update_one(filter, update).await?;
+        Ok(result.modified_count > 0)
+    }
+
+    async fn release_organization_slot(&self, user_id: &ObjectId) -> Result<()> {
+        let filter = doc! {
+            "_id": user_id,
+            "owned_organizations": { "$gt": 0 },
+        };
+        let update = doc! { "$inc": { "owned_organizations": -1 } };
+
+        self.collection.update_one(filter, update).await?;
         Ok(())
     }
+
+    async fn delete_user(&self, email: &str) -> Result<u64> {
+        let filter = doc! { "email": email };
+        let result = self.collection.delete_one(filter).await?;
+        Ok(result.deleted_count)
+    }
+
+    async fn update_user_by_id(
+        &self,
+        user_id: &ObjectId,
+        user: UpdateUserRequest,
+    ) -> Result<Option<User>> {
+        self.update_matching(doc! { "_id": user_id }, user).await
+    }
+
+    async fn delete_user_by_id(&self, user_id: &ObjectId) -> Result<Option<User>> {
+        self.collection
+            .find_one_and_delete(doc! { "_id": user_id })
+            .await
+    }
+}
+
+/// `created_at` is never part of the update; `updated_at` always is.
+fn profile_update_document(user: &UpdateUserRequest) -> Result<Document> {
+    let mut update_doc = Document::new();
+    if let Some(name) = &user.name {
+        update_doc.insert("name", name);
+    }
+    update_doc.insert("updated_at", to_bson(&Utc::now())?);
+    Ok(update_doc)
+}
+
+/// Documents written before versioning have no `version` field and read as 0.
+pub fn version_filter(version: u32) -> Bson {
+    if version == 0 {
+        Bson::Document(doc! { "$in": [0, Bson::Null] })
+    } else {
+        Bson::Int64(version.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn profile_updates_bump_updated_at_and_keep_created_at() {
+        let before = Utc::now();
+        let update = UpdateUserRequest {
+            name: Some("Ana".to_string()),
+            version: None,
+            extra: HashMap::new(),
+        };
+
+        let update_doc = profile_update_document(&update).unwrap();
+
+        assert_eq!(update_doc.get_str("name").unwrap(), "Ana");
+        assert!(!update_doc.contains_key("created_at"));
+        let updated_at: DateTime<Utc> = update_doc.get_str("updated_at").unwrap().parse().unwrap();
+        assert!(updated_at >= before);
+    }
 }
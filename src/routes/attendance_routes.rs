@@ -0,0 +1,24 @@
+use crate::config::json::configure_json;
+use crate::handlers::attendance_handler::{
+    create_class_handler, mark_class_attendance_handler, record_attendance_batch_handler,
+};
+use crate::services::attendance_service::AttendanceService;
+use actix_web::web;
+use std::sync::Arc;
+
+pub fn configure_attendance_routes(
+    cfg: &mut web::ServiceConfig,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+) {
+    cfg.service(
+        web::scope("/attendance")
+            .app_data(attendance_service)
+            .app_data(configure_json())
+            .route("/batch", web::post().to(record_attendance_batch_handler))
+            .route("/classes", web::post().to(create_class_handler))
+            .route(
+                "/classes/{class_id}",
+                web::post().to(mark_class_attendance_handler),
+            ),
+    );
+}
@@ -2,21 +2,43 @@ use actix_web::web;
 use std::sync::Arc;
 
 use crate::{
-    config::cors::configure_cors,
-    handlers::auth_handler::{jwt_login_handler, logout_user_handler, register_user_handler},
-    services::user_service::UserService,
+    config::json::configure_json,
+    handlers::auth_handler::{
+        email_available_handler, jwt_login_handler, logout_user_handler, register_user_handler,
+        request_password_reset_handler, reset_password_handler, revoke_sessions_handler,
+        validate_registration_handler, verify_email_handler,
+    },
+    services::{password_reset_service::PasswordResetService, user_service::UserService},
 };
 
 pub fn configure_auth_routes(
     cfg: &mut web::ServiceConfig,
     user_service_data: web::Data<Arc<UserService>>,
+    password_reset_service_data: web::Data<Arc<PasswordResetService>>,
 ) {
     cfg.service(
         web::scope("/auth")
-            .wrap(configure_cors())
             .app_data(user_service_data)
+            .app_data(password_reset_service_data)
+            .app_data(configure_json())
+            .route("/email-available", web::get().to(email_available_handler))
             .route("/login", web::post().to(jwt_login_handler))
             .route("/logout", web::delete().to(logout_user_handler))
-            .route("/register", web::post().to(register_user_handler)),
+            .route("/logout-all", web::post().to(revoke_sessions_handler))
+            .route("/register", web::post().to(register_user_handler))
+            .route("/sessions", web::delete().to(revoke_sessions_handler))
+            .route(
+                "/validate-registration",
+                web::post().to(validate_registration_handler),
+            )
+            .route("/verify", web::get().to(verify_email_handler))
+            .route(
+                "/password-reset/request",
+                web::post().to(request_password_reset_handler),
+            )
+            .route(
+                "/password-reset/confirm",
+                web::post().to(reset_password_handler),
+            ),
     );
 }
@@ -0,0 +1,21 @@
+use crate::config::json::configure_json;
+use crate::handlers::excuse_handler::{
+    approve_excuse_handler, reject_excuse_handler, submit_excuse_handler,
+};
+use crate::services::excuse_service::ExcuseService;
+use actix_web::web;
+use std::sync::Arc;
+
+pub fn configure_excuse_routes(
+    cfg: &mut web::ServiceConfig,
+    excuse_service: web::Data<Arc<ExcuseService>>,
+) {
+    cfg.service(
+        web::scope("/excuses")
+            .app_data(excuse_service)
+            .app_data(configure_json())
+            .route("", web::post().to(submit_excuse_handler))
+            .route("/{id}/approve", web::post().to(approve_excuse_handler))
+            .route("/{id}/reject", web::post().to(reject_excuse_handler)),
+    );
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::locale_handler::get_locale_bundle_handler;
+
+pub fn configure_locale_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/locales").route("/{lang}", web::get().to(get_locale_bundle_handler)));
+}
@@ -0,0 +1,14 @@
+use crate::handlers::attendance_handler::get_member_attendance_summary_handler;
+use crate::services::attendance_service::AttendanceService;
+use actix_web::web;
+use std::sync::Arc;
+
+pub fn configure_member_routes(
+    cfg: &mut web::ServiceConfig,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+) {
+    cfg.service(web::scope("/members").app_data(attendance_service).route(
+        "/{id}/attendance/summary",
+        web::get().to(get_member_attendance_summary_handler),
+    ));
+}
@@ -1,3 +1,7 @@
+pub mod attendance_routes;
 pub mod auth_routes;
+pub mod excuse_routes;
+pub mod locale_routes;
+pub mod member_routes;
 pub mod organization_routes;
 pub mod user_routes;
@@ -1,24 +1,71 @@
-use crate::config::cors::configure_cors;
+use crate::config::json::{configure_json, configure_payload};
+use crate::handlers::attendance_handler::{
+    export_attendance_summary_handler, get_attendance_summary_handler, get_today_attendance_handler,
+};
 use crate::handlers::organization_handler::{
-    create_organization_handler, delete_organization_handler, get_all_organizations_handler,
-    get_organization_handler, update_organization_handler,
+    count_organizations_handler, create_organization_handler, delete_organization_handler,
+    get_all_organizations_handler, get_organization_audit_handler, get_organization_handler,
+    transfer_ownership_handler, update_organization_handler,
+};
+use crate::handlers::organization_member_handler::{
+    get_members_handler, import_members_handler, invite_member_handler, join_organization_handler,
+    leave_organization_handler, update_member_role_handler,
+};
+use crate::services::{
+    attendance_service::AttendanceService, organization_member_service::OrganizationMemberService,
+    organization_service::OrganizationService,
 };
-use crate::services::organization_service::OrganizationService;
 use actix_web::web;
 use std::sync::Arc;
 
 pub fn configure_organization_routes(
     cfg: &mut web::ServiceConfig,
     organization_service: web::Data<Arc<OrganizationService>>,
+    attendance_service: web::Data<Arc<AttendanceService>>,
+    organization_member_service: web::Data<Arc<OrganizationMemberService>>,
 ) {
     cfg.service(
         web::scope("/organizations")
             .app_data(organization_service)
+            .app_data(attendance_service)
+            .app_data(organization_member_service)
+            .app_data(configure_json())
+            .app_data(configure_payload())
             .route("/new", web::post().to(create_organization_handler))
+            .route("", web::get().to(get_all_organizations_handler))
             .route("/all", web::get().to(get_all_organizations_handler))
+            .route("/count", web::get().to(count_organizations_handler))
             .route("/{id}", web::get().to(get_organization_handler))
             .route("/{id}", web::put().to(update_organization_handler))
             .route("/{id}", web::delete().to(delete_organization_handler))
-            .wrap(configure_cors()),
+            .route("/{id}/audit", web::get().to(get_organization_audit_handler))
+            .route(
+                "/{id}/attendance/summary",
+                web::get().to(get_attendance_summary_handler),
+            )
+            .route(
+                "/{id}/attendance/summary/export",
+                web::get().to(export_attendance_summary_handler),
+            )
+            .route(
+                "/{id}/attendance/today",
+                web::get().to(get_today_attendance_handler),
+            )
+            .route("/{id}/join", web::post().to(join_organization_handler))
+            .route("/{id}/leave", web::post().to(leave_organization_handler))
+            .route("/{id}/transfer", web::post().to(transfer_ownership_handler))
+            .route("/{id}/members", web::get().to(get_members_handler))
+            .route(
+                "/{id}/members/import",
+                web::post().to(import_members_handler),
+            )
+            .route(
+                "/{id}/members/invite",
+                web::post().to(invite_member_handler),
+            )
+            .route(
+                "/{id}/members/{member_id}/role",
+                web::put().to(update_member_role_handler),
+            ),
     );
 }
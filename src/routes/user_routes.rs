@@ -1,20 +1,47 @@
+use crate::config::json::configure_json;
 use crate::handlers::user_handler::{
-    delete_user_handler, get_all_users_handler, get_user_handler, update_user_handler,
+    count_users_handler, delete_user_by_id_handler, delete_user_handler, get_all_users_handler,
+    get_dormant_users_handler, get_user_handler, me_handler, update_password_handler,
+    update_subscription_handler, update_user_by_id_handler, update_user_handler,
+    update_user_status_handler,
 };
-use crate::services::user_service::UserService;
+use crate::services::{organization_service::OrganizationService, user_service::UserService};
 use actix_web::web;
 use std::sync::Arc;
 
 pub fn configure_user_routes(
     cfg: &mut web::ServiceConfig,
     user_service_data: web::Data<Arc<UserService>>,
+    organization_service_data: web::Data<Arc<OrganizationService>>,
 ) {
+    cfg.service(
+        web::resource("/me")
+            .app_data(user_service_data.clone())
+            .route(web::get().to(me_handler)),
+    );
+
     cfg.service(
         web::scope("/users")
             .app_data(user_service_data)
+            .app_data(organization_service_data)
+            .app_data(configure_json())
+            .route("", web::get().to(get_all_users_handler))
             .route("/all", web::get().to(get_all_users_handler))
+            .route("/count", web::get().to(count_users_handler))
+            .route("/dormant", web::get().to(get_dormant_users_handler))
+            .route("/me", web::get().to(me_handler))
+            .route("/id/{id}", web::put().to(update_user_by_id_handler))
+            .route("/id/{id}", web::patch().to(update_user_by_id_handler))
+            .route("/id/{id}", web::delete().to(delete_user_by_id_handler))
             .route("/{email}", web::get().to(get_user_handler))
             .route("/{email}", web::put().to(update_user_handler))
-            .route("/{email}", web::delete().to(delete_user_handler)),
+            .route("/{email}", web::patch().to(update_user_handler))
+            .route("/{email}", web::delete().to(delete_user_handler))
+            .route("/{email}/password", web::post().to(update_password_handler))
+            .route(
+                "/{email}/subscription",
+                web::put().to(update_subscription_handler),
+            )
+            .route("/{email}/status", web::put().to(update_user_status_handler)),
     );
 }
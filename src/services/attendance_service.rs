@@ -0,0 +1,951 @@
+use crate::{
+    models::{
+        attendance_model::Attendance, class_session_model::ClassSession,
+        organization_model::Organization,
+    },
+    repositories::{
+        attendance_repository::AttendanceRepository,
+        class_session_repository::ClassSessionRepository,
+        organization_member_repository::OrganizationMemberRepository,
+        organization_repository::OrganizationRepository,
+        user_repository::{UserRepository, UserRepositoryTrait},
+    },
+    types::{
+        models::{
+            attendance::{
+                attendance_method::AttendanceMethod, attendance_status::AttendanceStatus,
+                attendance_type::AttendanceType,
+            },
+            user::role::Role,
+        },
+        requests::{
+            attendance::{
+                batch_attendance_request::BatchAttendanceRequest,
+                class_attendance_request::{ClassAttendanceMark, ClassAttendanceRequest},
+                create_class_request::CreateClassRequest,
+            },
+            common::pagination_query::PaginationQuery,
+        },
+        responses::{
+            api_response::PaginatedResponse, attendance_summary::AttendanceSummary,
+            batch_attendance_response::BatchAttendanceResponse,
+            class_attendance_response::ClassAttendanceResponse,
+            member_attendance_status::MemberAttendanceStatus,
+            member_attendance_summary::MemberAttendanceSummary,
+        },
+    },
+    utils::{
+        locale_utils::Messages,
+        time_utils::{late_grace_period, local_day_bounds, utc_day_start},
+    },
+};
+use anyhow::{Result, anyhow};
+use bson::oid::ObjectId;
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::{HashMap, HashSet, hash_map::Entry},
+    fmt,
+    sync::Arc,
+};
+
+pub const MAX_BATCH_SIZE: usize = 500;
+const CLOCK_SKEW_MINUTES: i64 = 5;
+
+#[derive(Debug)]
+pub enum AttendanceServiceError {
+    OrganizationNotFound,
+    MemberNotFound,
+    ClassNotFound,
+    Forbidden,
+    InvalidInstructor,
+    CheckInNotOpen,
+    CheckInClosed,
+    BatchTooLarge,
+}
+
+impl fmt::Display for AttendanceServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttendanceServiceError::OrganizationNotFound => write!(f, "Organization not found"),
+            AttendanceServiceError::MemberNotFound => write!(f, "Member not found"),
+            AttendanceServiceError::ClassNotFound => write!(f, "Class not found"),
+            AttendanceServiceError::Forbidden => {
+                write!(f, "Not allowed to record attendance for this organization")
+            }
+            AttendanceServiceError::InvalidInstructor => {
+                write!(f, "Instructor is not staff of this organization")
+            }
+            AttendanceServiceError::CheckInNotOpen => write!(f, "Check-in is not open yet"),
+            AttendanceServiceError::CheckInClosed => write!(f, "Check-in has closed"),
+            AttendanceServiceError::BatchTooLarge => {
+                write!(f, "Batch exceeds {MAX_BATCH_SIZE} entries")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AttendanceServiceError {}
+
+pub struct AttendanceService {
+    attendance_repository: Arc<AttendanceRepository>,
+    organization_repository: Arc<OrganizationRepository>,
+    organization_member_repository: Arc<OrganizationMemberRepository>,
+    class_session_repository: Arc<ClassSessionRepository>,
+    user_repository: Arc<UserRepository>,
+}
+
+impl AttendanceService {
+    pub fn new(
+        attendance_repository: Arc<AttendanceRepository>,
+        organization_repository: Arc<OrganizationRepository>,
+        organization_member_repository: Arc<OrganizationMemberRepository>,
+        class_session_repository: Arc<ClassSessionRepository>,
+        user_repository: Arc<UserRepository>,
+    ) -> Self {
+        Self {
+            attendance_repository,
+            organization_repository,
+            organization_member_repository,
+            class_session_repository,
+            user_repository,
+        }
+    }
+
+    async fn recorder_id(&self, email: &str) -> Result<ObjectId> {
+        self.user_repository
+            .find_user("email", email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(AttendanceServiceError::Forbidden))
+    }
+
+    /// Whether the user owns the organization or is a member holding one of
+    /// `roles`.
+    async fn can_record(
+        &self,
+        organization: &Organization,
+        organization_id: &ObjectId,
+        user_id: &ObjectId,
+        roles: &[Role],
+    ) -> Result<bool> {
+        Ok(*user_id == organization.owner_id
+            || self
+                .organization_member_repository
+                .has_role(organization_id, user_id, roles)
+                .await?)
+    }
+
+    /// Loads the organization if the viewer owns it or is a member holding
+    /// one of `roles`.
+    async fn viewable_organization(
+        &self,
+        organization_id: &ObjectId,
+        viewer_email: &str,
+        roles: &[Role],
+    ) -> Result<Organization> {
+        let organization = self.find_organization(organization_id).await?;
+        let viewer_id = self.recorder_id(viewer_email).await?;
+        if !self
+            .can_record(&organization, organization_id, &viewer_id, roles)
+            .await?
+        {
+            return Err(anyhow!(AttendanceServiceError::Forbidden));
+        }
+        Ok(organization)
+    }
+
+    async fn find_organization(&self, organization_id: &ObjectId) -> Result<Organization> {
+        self.organization_repository
+            .find_organization_by_id(organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(AttendanceServiceError::OrganizationNotFound))
+    }
+
+    /// Schedules a class. Only the organization's owner and admins may do so.
+    pub async fn create_class(
+        &self,
+        request: CreateClassRequest,
+        creator_email: &str,
+    ) -> Result<ClassSession> {
+        let organization_id = ObjectId::parse_str(&request.organization_id)
+            .map_err(|_| anyhow!(AttendanceServiceError::OrganizationNotFound))?;
+        let organization = self.find_organization(&organization_id).await?;
+        let creator_id = self.recorder_id(creator_email).await?;
+        if !self
+            .can_record(&organization, &organization_id, &creator_id, &[Role::Admin])
+            .await?
+        {
+            return Err(anyhow!(AttendanceServiceError::Forbidden));
+        }
+
+        let instructor_id = match &request.instructor_id {
+            Some(instructor_id) => {
+                let instructor_id = ObjectId::parse_str(instructor_id)
+                    .map_err(|_| anyhow!(AttendanceServiceError::InvalidInstructor))?;
+                if !self
+                    .organization_member_repository
+                    .has_role(
+                        &organization_id,
+                        &instructor_id,
+                        &[Role::Admin, Role::Staff],
+                    )
+                    .await?
+                {
+                    return Err(anyhow!(AttendanceServiceError::InvalidInstructor));
+                }
+                Some(instructor_id)
+            }
+            None => None,
+        };
+
+        let class_session = ClassSession {
+            _id: None,
+            organization_id,
+            name: request.name.trim().to_string(),
+            instructor_id,
+            starts_at: request.starts_at,
+            check_in_opens_minutes_before: request.check_in_opens_minutes_before,
+            check_in_closes_minutes_after: request.check_in_closes_minutes_after,
+            created_at: Utc::now(),
+        };
+        Ok(self
+            .class_session_repository
+            .create_class(&class_session)
+            .await?)
+    }
+
+    /// Today's status of every member, for the owner, admins and staff.
+    pub async fn today(
+        &self,
+        org_id: &str,
+        class_id: Option<&str>,
+        viewer_email: &str,
+        pagination: &PaginationQuery,
+    ) -> Result<PaginatedResponse<MemberAttendanceStatus>> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .viewable_organization(&organization_id, viewer_email, &[Role::Admin, Role::Staff])
+            .await?;
+
+        let class_id = match class_id {
+            Some(class_id) => {
+                let class_id = ObjectId::parse_str(class_id)?;
+                self.class_session_repository
+                    .find_class(&organization_id, &class_id)
+                    .await?
+                    .ok_or_else(|| anyhow!(AttendanceServiceError::ClassNotFound))?;
+                Some(class_id)
+            }
+            None => None,
+        };
+
+        let (start, end) = local_day_bounds(organization.tz(), Utc::now());
+
+        let members = self
+            .organization_member_repository
+            .find_members_with_attendance(
+                &organization_id,
+                class_id.as_ref(),
+                start,
+                end,
+                pagination.skip(),
+                pagination.per_page(),
+            )
+            .await?;
+        let total = self
+            .organization_member_repository
+            .count_members(&organization_id)
+            .await?;
+
+        Ok(PaginatedResponse::new(
+            members,
+            total,
+            pagination.page(),
+            pagination.per_page(),
+        ))
+    }
+
+    /// Records a kiosk batch. Invalid entries and members already recorded for
+    /// that local day are skipped without failing the rest of the batch.
+    pub async fn record_batch(
+        &self,
+        request: BatchAttendanceRequest,
+        recorder_email: &str,
+        messages: &Messages,
+    ) -> Result<BatchAttendanceResponse> {
+        if request.entries.len() > MAX_BATCH_SIZE {
+            return Err(anyhow!(AttendanceServiceError::BatchTooLarge));
+        }
+
+        let organization_id = ObjectId::parse_str(&request.organization_id)
+            .map_err(|_| anyhow!(AttendanceServiceError::OrganizationNotFound))?;
+        let organization = self.find_organization(&organization_id).await?;
+        let recorder_id = self.recorder_id(recorder_email).await?;
+        if !self
+            .can_record(
+                &organization,
+                &organization_id,
+                &recorder_id,
+                &[Role::Admin],
+            )
+            .await?
+        {
+            return Err(anyhow!(AttendanceServiceError::Forbidden));
+        }
+        let tz = organization.tz();
+
+        let class_session = match &request.class_id {
+            Some(class_id) => {
+                let class_id = ObjectId::parse_str(class_id)
+                    .map_err(|_| anyhow!(AttendanceServiceError::ClassNotFound))?;
+                let class_session = self
+                    .class_session_repository
+                    .find_class(&organization_id, &class_id)
+                    .await?
+                    .ok_or_else(|| anyhow!(AttendanceServiceError::ClassNotFound))?;
+                Some(class_session)
+            }
+            None => None,
+        };
+        let late_after = class_session
+            .as_ref()
+            .map(|class_session| class_session.starts_at + late_grace_period());
+        let class_id = class_session
+            .as_ref()
+            .and_then(|class_session| class_session._id);
+        let (opens_at, closes_at) = class_session
+            .as_ref()
+            .map(|class_session| class_session.check_in_window(tz))
+            .unwrap_or_default();
+
+        let requested_ids: Vec<ObjectId> = request
+            .entries
+            .iter()
+            .filter_map(|entry| ObjectId::parse_str(&entry.member_id).ok())
+            .collect();
+        let known_members = self
+            .organization_member_repository
+            .find_member_ids(&organization_id, &requested_ids)
+            .await?;
+
+        let now = Utc::now();
+        let mut recorded_by_day: HashMap<DateTime<Utc>, HashSet<ObjectId>> = HashMap::new();
+        let mut report = BatchAttendanceResponse::default();
+        let mut accepted = Vec::new();
+        let mut records = Vec::new();
+
+        for (index, entry) in request.entries.into_iter().enumerate() {
+            let member_id = match ObjectId::parse_str(&entry.member_id) {
+                Ok(member_id) if known_members.contains(&member_id) => member_id,
+                _ => {
+                    report.push_skipped(
+                        index,
+                        messages.get_attendance_message(
+                            "batch.unknown_member",
+                            "Member not found in this organization.",
+                        ),
+                    );
+                    continue;
+                }
+            };
+
+            let clock_in = match accepted_clock_in(entry.clock_in, now, messages) {
+                Ok(clock_in) => clock_in,
+                Err(error) => {
+                    report.push_skipped(index, error);
+                    continue;
+                }
+            };
+
+            if let Some(err) = outside_check_in_window(clock_in, opens_at, closes_at) {
+                report.push_skipped(index, window_message(&err, messages));
+                continue;
+            }
+
+            if entry.location.as_ref().is_some_and(|location| {
+                !(-90.0..=90.0).contains(&location.lat)
+                    || !(-180.0..=180.0).contains(&location.long)
+            }) {
+                report.push_skipped(
+                    index,
+                    messages.get_attendance_message("batch.invalid_location", "Invalid location."),
+                );
+                continue;
+            }
+
+            // Class check-ins are unique per UTC day and class; plain ones per
+            // local day across the organization.
+            let (start, end) = local_day_bounds(tz, clock_in);
+            let day = class_id.map_or(start, |_| utc_day_start(clock_in));
+            let recorded = match recorded_by_day.entry(day) {
+                Entry::Occupied(recorded) => recorded.into_mut(),
+                Entry::Vacant(slot) => slot.insert(match class_id {
+                    Some(class_id) => {
+                        self.attendance_repository
+                            .find_checked_in_members(class_id, day)
+                            .await?
+                    }
+                    None => {
+                        self.attendance_repository
+                            .find_recorded_members(organization_id, start, end)
+                            .await?
+                    }
+                }),
+            };
+            if !recorded.insert(member_id) {
+                let message = match class_id {
+                    Some(_) => already_checked_in(messages),
+                    None => messages.get_attendance_message(
+                        "batch.duplicate",
+                        "Attendance already recorded for this day.",
+                    ),
+                };
+                report.push_skipped(index, message);
+                continue;
+            }
+
+            let status = late_status(entry.status, clock_in, late_after);
+
+            accepted.push(index);
+            records.push(Attendance {
+                id: None,
+                user_id: member_id,
+                organization_id,
+                class_id,
+                check_in_date: class_id.map(|_| day),
+                attendance_type: AttendanceType::SingleMark,
+                status,
+                clock_in: Some(clock_in),
+                clock_out: None,
+                method: entry.method,
+                location: entry.location,
+                created_at: clock_in,
+                updated_at: now,
+            });
+        }
+
+        let rejected = self.record_check_ins(&records).await?;
+
+        for (position, index) in accepted.into_iter().enumerate() {
+            if rejected.contains(&position) {
+                report.push_skipped(index, already_checked_in(messages));
+            } else {
+                report.push_success(index);
+            }
+        }
+        report.entries.sort_by_key(|entry| entry.index);
+
+        Ok(report)
+    }
+
+    /// Marks a class roster at once. Members outside the class's organization
+    /// are rejected without failing the rest of the roster. Only the class's
+    /// instructor and the organization's owner, admins and staff may mark it.
+    pub async fn mark_class_attendance(
+        &self,
+        class_id: &str,
+        marker_email: &str,
+        request: ClassAttendanceRequest,
+        messages: &Messages,
+    ) -> Result<ClassAttendanceResponse> {
+        if request.members.len() > MAX_BATCH_SIZE {
+            return Err(anyhow!(AttendanceServiceError::BatchTooLarge));
+        }
+
+        let class_id = ObjectId::parse_str(class_id)
+            .map_err(|_| anyhow!(AttendanceServiceError::ClassNotFound))?;
+        let class_session = self
+            .class_session_repository
+            .find_class_by_id(&class_id)
+            .await?
+            .ok_or_else(|| anyhow!(AttendanceServiceError::ClassNotFound))?;
+        let organization_id = class_session.organization_id;
+        let organization = self.find_organization(&organization_id).await?;
+
+        let marker_id = self.recorder_id(marker_email).await?;
+        let allowed = class_session.instructor_id == Some(marker_id)
+            || self
+                .can_record(
+                    &organization,
+                    &organization_id,
+                    &marker_id,
+                    &[Role::Admin, Role::Staff],
+                )
+                .await?;
+        if !allowed {
+            return Err(anyhow!(AttendanceServiceError::Forbidden));
+        }
+
+        let requested_ids: Vec<ObjectId> = request
+            .members
+            .iter()
+            .filter_map(|mark| ObjectId::parse_str(&mark.member_id).ok())
+            .collect();
+        let known_members = self
+            .organization_member_repository
+            .find_member_ids(&organization_id, &requested_ids)
+            .await?;
+
+        let now = Utc::now();
+        let (opens_at, closes_at) = class_session.check_in_window(organization.tz());
+        if let Some(err) = outside_check_in_window(now, opens_at, closes_at) {
+            return Err(anyhow!(err));
+        }
+        let check_in_date = utc_day_start(now);
+        let checked_in = self
+            .attendance_repository
+            .find_checked_in_members(class_id, check_in_date)
+            .await?;
+        let (mut report, accepted) =
+            screen_roster(request.members, &known_members, &checked_in, messages);
+        let records: Vec<Attendance> = accepted
+            .iter()
+            .map(|(_, member_id, status)| Attendance {
+                id: None,
+                user_id: *member_id,
+                organization_id,
+                class_id: Some(class_id),
+                check_in_date: Some(check_in_date),
+                attendance_type: AttendanceType::SingleMark,
+                status: status.clone(),
+                clock_in: Some(now),
+                clock_out: None,
+                method: AttendanceMethod::Manual,
+                location: None,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        let rejected = self.record_check_ins(&records).await?;
+
+        for (position, (member_id, _, _)) in accepted.into_iter().enumerate() {
+            if rejected.contains(&position) {
+                report.push_rejected(member_id, already_checked_in(messages));
+            } else {
+                report.push_success(member_id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Returns the positions of records rejected by the unique check-in index,
+    /// which means a concurrent request checked the same member in after the
+    /// duplicate check above ran.
+    async fn record_check_ins(&self, records: &[Attendance]) -> Result<HashSet<usize>> {
+        Ok(self.attendance_repository.record_many(records).await?)
+    }
+
+    /// Per-member counts for the organization, for the owner, admins and staff.
+    pub async fn summary(
+        &self,
+        org_id: &str,
+        viewer_email: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<AttendanceSummary>> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        self.viewable_organization(&organization_id, viewer_email, &[Role::Admin, Role::Staff])
+            .await?;
+
+        self.attendance_repository
+            .summarize_by_member(organization_id, from, to)
+            .await
+            .map_err(anyhow::Error::from)
+    }
+
+    /// The organization summary as CSV, with headers in the organization's
+    /// default language so exports read the same whoever downloads them.
+    /// Only the owner and admins may export.
+    pub async fn summary_csv(
+        &self,
+        org_id: &str,
+        viewer_email: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<String> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .viewable_organization(&organization_id, viewer_email, &[Role::Admin])
+            .await?;
+
+        let rows = self
+            .attendance_repository
+            .summarize_by_member(organization_id, from, to)
+            .await?;
+
+        summary_csv(&rows, &organization.messages())
+    }
+
+    /// A member's counts, readable by the member and by their organization's
+    /// owner, admins and staff.
+    pub async fn member_summary(
+        &self,
+        member_id: &str,
+        viewer_email: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<MemberAttendanceSummary> {
+        let member_id = ObjectId::parse_str(member_id)?;
+        let member = self
+            .organization_member_repository
+            .find_member_by_id(&member_id)
+            .await?
+            .ok_or_else(|| anyhow!(AttendanceServiceError::MemberNotFound))?;
+        let viewer_id = self.recorder_id(viewer_email).await?;
+        if member.user_id != Some(viewer_id) {
+            self.viewable_organization(
+                &member.organization_id,
+                viewer_email,
+                &[Role::Admin, Role::Staff],
+            )
+            .await?;
+        }
+
+        let summary = self
+            .attendance_repository
+            .summarize_member(member_id, from, to)
+            .await?;
+
+        Ok(MemberAttendanceSummary::new(summary))
+    }
+}
+
+/// Clients may only correct for clock skew: a `clock_in` further from the
+/// server's time than that is rejected, so backdating cannot dodge the late
+/// flag or the check-in window.
+fn accepted_clock_in(
+    clock_in: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    messages: &Messages,
+) -> Result<DateTime<Utc>, String> {
+    let skew = Duration::minutes(CLOCK_SKEW_MINUTES);
+    match clock_in {
+        None => Ok(now),
+        Some(clock_in) if clock_in > now + skew => Err(messages.get_attendance_message(
+            "batch.future_clock_in",
+            "Clock-in time cannot be in the future.",
+        )),
+        Some(clock_in) if clock_in < now - skew => Err(messages.get_attendance_message(
+            "batch.backdated_clock_in",
+            "Clock-in time is too far in the past.",
+        )),
+        Some(clock_in) => Ok(clock_in),
+    }
+}
+
+fn outside_check_in_window(
+    clock_in: DateTime<Utc>,
+    opens_at: Option<DateTime<Utc>>,
+    closes_at: Option<DateTime<Utc>>,
+) -> Option<AttendanceServiceError> {
+    if opens_at.is_some_and(|opens_at| clock_in < opens_at) {
+        Some(AttendanceServiceError::CheckInNotOpen)
+    } else if closes_at.is_some_and(|closes_at| clock_in > closes_at) {
+        Some(AttendanceServiceError::CheckInClosed)
+    } else {
+        None
+    }
+}
+
+pub fn window_message(err: &AttendanceServiceError, messages: &Messages) -> String {
+    match err {
+        AttendanceServiceError::CheckInNotOpen => messages.get_attendance_message(
+            "batch.check_in_not_open",
+            "Check-in for this class is not open yet.",
+        ),
+        _ => messages.get_attendance_message(
+            "batch.check_in_closed",
+            "Check-in for this class has closed.",
+        ),
+    }
+}
+
+fn late_status(
+    status: AttendanceStatus,
+    clock_in: DateTime<Utc>,
+    late_after: Option<DateTime<Utc>>,
+) -> AttendanceStatus {
+    match late_after {
+        Some(late_after) if status == AttendanceStatus::Present && clock_in > late_after => {
+            AttendanceStatus::Late
+        }
+        _ => status,
+    }
+}
+
+/// Rejects roster entries that are not members of the organization, already
+/// checked in today or listed twice, and returns the rest in roster order.
+fn screen_roster(
+    marks: Vec<ClassAttendanceMark>,
+    known_members: &HashSet<ObjectId>,
+    checked_in: &HashSet<ObjectId>,
+    messages: &Messages,
+) -> (
+    ClassAttendanceResponse,
+    Vec<(String, ObjectId, AttendanceStatus)>,
+) {
+    let mut report = ClassAttendanceResponse::default();
+    let mut marked = HashSet::new();
+    let mut accepted = Vec::new();
+
+    for mark in marks {
+        let member_id = match ObjectId::parse_str(&mark.member_id) {
+            Ok(member_id) if known_members.contains(&member_id) => member_id,
+            _ => {
+                report.push_rejected(
+                    mark.member_id,
+                    messages.get_attendance_message(
+                        "batch.unknown_member",
+                        "Member not found in this organization.",
+                    ),
+                );
+                continue;
+            }
+        };
+
+        if checked_in.contains(&member_id) {
+            report.push_rejected(mark.member_id, already_checked_in(messages));
+            continue;
+        }
+
+        if !marked.insert(member_id) {
+            report.push_rejected(
+                mark.member_id,
+                messages.get_attendance_message(
+                    "class.duplicate_member",
+                    "Member is listed more than once.",
+                ),
+            );
+            continue;
+        }
+
+        accepted.push((mark.member_id, member_id, mark.status));
+    }
+
+    (report, accepted)
+}
+
+fn already_checked_in(messages: &Messages) -> String {
+    messages.get_attendance_message(
+        "already_checked_in",
+        "Member has already checked in to this class today.",
+    )
+}
+
+fn summary_csv(rows: &[AttendanceSummary], messages: &Messages) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        messages.get_attendance_message("export.user_id", "User ID"),
+        messages.get_attendance_message("export.present", "Present"),
+        messages.get_attendance_message("export.absent", "Absent"),
+        messages.get_attendance_message("export.late", "Late"),
+        messages.get_attendance_message("export.excused", "Excused"),
+    ])?;
+    for row in rows {
+        writer.write_record([
+            row.user_id.to_hex(),
+            row.present.to_string(),
+            row.absent.to_string(),
+            row.late.to_string(),
+            row.excused.to_string(),
+        ])?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::lang::Lang;
+
+    fn mark(member_id: &str) -> ClassAttendanceMark {
+        ClassAttendanceMark {
+            member_id: member_id.to_string(),
+            status: AttendanceStatus::Present,
+        }
+    }
+
+    #[test]
+    fn clock_in_defaults_to_the_server_time() {
+        let messages = Messages::new(Lang::En);
+        let now = Utc::now();
+
+        assert_eq!(accepted_clock_in(None, now, &messages), Ok(now));
+    }
+
+    #[test]
+    fn clock_in_may_only_differ_from_the_server_by_the_allowed_skew() {
+        let messages = Messages::new(Lang::En);
+        let now = Utc::now();
+        let skew = Duration::minutes(CLOCK_SKEW_MINUTES);
+
+        assert!(accepted_clock_in(Some(now - skew), now, &messages).is_ok());
+        assert!(accepted_clock_in(Some(now + skew), now, &messages).is_ok());
+        assert!(accepted_clock_in(Some(now - Duration::hours(1)), now, &messages).is_err());
+        assert!(accepted_clock_in(Some(now + Duration::hours(1)), now, &messages).is_err());
+    }
+
+    #[test]
+    fn a_backdated_check_in_after_the_grace_period_cannot_avoid_the_late_flag() {
+        let messages = Messages::new(Lang::En);
+        let starts_at = Utc::now() - Duration::hours(1);
+        let late_after = Some(starts_at + late_grace_period());
+        let now = Utc::now();
+
+        let backdated = accepted_clock_in(Some(starts_at), now, &messages);
+        let clock_in = accepted_clock_in(None, now, &messages).unwrap();
+
+        assert!(backdated.is_err());
+        assert_eq!(
+            late_status(AttendanceStatus::Present, clock_in, late_after),
+            AttendanceStatus::Late
+        );
+    }
+
+    #[test]
+    fn only_present_check_ins_after_the_grace_period_are_late() {
+        let starts_at = Utc::now();
+        let late_after = Some(starts_at + late_grace_period());
+        let on_time = starts_at;
+        let late = starts_at + late_grace_period() + Duration::minutes(1);
+
+        assert_eq!(
+            late_status(AttendanceStatus::Present, on_time, late_after),
+            AttendanceStatus::Present
+        );
+        assert_eq!(
+            late_status(AttendanceStatus::Present, late, late_after),
+            AttendanceStatus::Late
+        );
+        assert_eq!(
+            late_status(AttendanceStatus::Absent, late, late_after),
+            AttendanceStatus::Absent
+        );
+        assert_eq!(
+            late_status(AttendanceStatus::Present, late, None),
+            AttendanceStatus::Present
+        );
+    }
+
+    fn window(
+        starts_at: DateTime<Utc>,
+        opens_before: u32,
+        closes_after: u32,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        let class_session = ClassSession {
+            _id: None,
+            organization_id: ObjectId::new(),
+            name: "Algorithms".to_string(),
+            instructor_id: None,
+            starts_at,
+            check_in_opens_minutes_before: Some(opens_before),
+            check_in_closes_minutes_after: Some(closes_after),
+            created_at: starts_at,
+        };
+        class_session.check_in_window(chrono_tz::Asia::Jakarta)
+    }
+
+    #[test]
+    fn check_ins_before_the_window_opens_are_rejected() {
+        let starts_at = Utc::now();
+        let (opens_at, closes_at) = window(starts_at, 15, 30);
+
+        let err = outside_check_in_window(starts_at - Duration::minutes(16), opens_at, closes_at);
+
+        assert!(matches!(err, Some(AttendanceServiceError::CheckInNotOpen)));
+    }
+
+    #[test]
+    fn check_ins_inside_the_window_are_accepted() {
+        let starts_at = Utc::now();
+        let (opens_at, closes_at) = window(starts_at, 15, 30);
+
+        for offset in [-15, 0, 30] {
+            let clock_in = starts_at + Duration::minutes(offset);
+            assert!(outside_check_in_window(clock_in, opens_at, closes_at).is_none());
+        }
+    }
+
+    #[test]
+    fn check_ins_after_the_window_closes_are_rejected() {
+        let starts_at = Utc::now();
+        let (opens_at, closes_at) = window(starts_at, 15, 30);
+
+        let err = outside_check_in_window(starts_at + Duration::minutes(31), opens_at, closes_at);
+
+        assert!(matches!(err, Some(AttendanceServiceError::CheckInClosed)));
+    }
+
+    #[test]
+    fn a_class_without_bounds_is_always_open() {
+        let clock_in = Utc::now();
+
+        assert!(outside_check_in_window(clock_in, None, None).is_none());
+    }
+
+    #[test]
+    fn a_non_member_is_rejected_while_the_rest_of_the_roster_is_marked() {
+        let messages = Messages::new(Lang::En);
+        let (first, second, outsider) = (ObjectId::new(), ObjectId::new(), ObjectId::new());
+        let known_members = HashSet::from([first, second]);
+        let roster = vec![
+            mark(&first.to_hex()),
+            mark(&outsider.to_hex()),
+            mark(&second.to_hex()),
+        ];
+
+        let (report, accepted) = screen_roster(roster, &known_members, &HashSet::new(), &messages);
+
+        assert_eq!(report.rejected, 1);
+        assert_eq!(report.results[0].member_id, outsider.to_hex());
+        let marked: Vec<ObjectId> = accepted.iter().map(|(_, id, _)| *id).collect();
+        assert_eq!(marked, vec![first, second]);
+    }
+
+    #[test]
+    fn checked_in_and_repeated_members_are_rejected() {
+        let messages = Messages::new(Lang::En);
+        let (present, repeated) = (ObjectId::new(), ObjectId::new());
+        let known_members = HashSet::from([present, repeated]);
+        let roster = vec![
+            mark(&present.to_hex()),
+            mark(&repeated.to_hex()),
+            mark(&repeated.to_hex()),
+            mark("not-an-id"),
+        ];
+
+        let (report, accepted) =
+            screen_roster(roster, &known_members, &HashSet::from([present]), &messages);
+
+        assert_eq!(report.rejected, 3);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].1, repeated);
+    }
+
+    #[test]
+    fn summary_csv_uses_the_given_language_for_headers() {
+        let user_id = ObjectId::new();
+        let rows = [AttendanceSummary {
+            user_id,
+            present: 3,
+            absent: 1,
+            late: 2,
+            excused: 0,
+        }];
+
+        let csv = summary_csv(&rows, &Messages::new(Lang::De)).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("Benutzer-ID,Anwesend,Abwesend,Verspätet,Entschuldigt")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(format!("{},3,1,2,0", user_id.to_hex()).as_str())
+        );
+        assert_eq!(lines.next(), None);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    models::audit_log_model::AuditLog,
+    repositories::audit_log_repository::AuditLogRepository,
+    types::{
+        requests::common::pagination_query::PaginationQuery,
+        responses::api_response::PaginatedResponse,
+    },
+};
+use anyhow::Result;
+use bson::oid::ObjectId;
+use log::error;
+use std::sync::Arc;
+
+pub struct AuditService {
+    audit_log_repository: Arc<AuditLogRepository>,
+}
+
+impl AuditService {
+    pub fn new(audit_log_repository: Arc<AuditLogRepository>) -> Self {
+        Self {
+            audit_log_repository,
+        }
+    }
+
+    /// Writes the entry in the background so auditing never delays or fails
+    /// the operation being recorded.
+    pub fn record(&self, log: AuditLog) {
+        let repository = self.audit_log_repository.clone();
+        actix_rt::spawn(async move {
+            if let Err(err) = repository.insert_log(&log).await {
+                error!("❌ Failed to write audit log: {:?}", err);
+            }
+        });
+    }
+
+    pub async fn get_organization_logs(
+        &self,
+        organization_id: &ObjectId,
+        pagination: &PaginationQuery,
+    ) -> Result<PaginatedResponse<AuditLog>> {
+        let logs = self
+            .audit_log_repository
+            .find_logs_by_organization(organization_id, pagination.skip(), pagination.per_page())
+            .await?;
+        let total = self
+            .audit_log_repository
+            .count_logs_by_organization(organization_id)
+            .await?;
+
+        Ok(PaginatedResponse::new(
+            logs,
+            total,
+            pagination.page(),
+            pagination.per_page(),
+        ))
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    models::{excuse_request_model::ExcuseRequest, organization_model::Organization},
+    repositories::{
+        excuse_request_repository::ExcuseRequestRepository,
+        organization_member_repository::OrganizationMemberRepository,
+        organization_repository::OrganizationRepository,
+        user_repository::{UserRepository, UserRepositoryTrait},
+    },
+    types::{
+        models::{attendance::excuse_status::ExcuseStatus, user::role::Role},
+        requests::attendance::submit_excuse_request::SubmitExcuseRequest,
+    },
+    utils::time_utils::local_date_bounds,
+};
+use anyhow::{Result, anyhow};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use std::{fmt, sync::Arc};
+
+#[derive(Debug)]
+pub enum ExcuseServiceError {
+    OrganizationNotFound,
+    NotMember,
+    Forbidden,
+    ExcuseNotFound,
+    AlreadyProcessed,
+}
+
+impl fmt::Display for ExcuseServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExcuseServiceError::OrganizationNotFound => write!(f, "Organization not found"),
+            ExcuseServiceError::NotMember => {
+                write!(f, "User is not a member of this organization")
+            }
+            ExcuseServiceError::Forbidden => {
+                write!(f, "Not allowed to review this organization's excuses")
+            }
+            ExcuseServiceError::ExcuseNotFound => write!(f, "Excuse request not found"),
+            ExcuseServiceError::AlreadyProcessed => {
+                write!(f, "Excuse request has already been processed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExcuseServiceError {}
+
+pub struct ExcuseService {
+    excuse_request_repository: Arc<ExcuseRequestRepository>,
+    organization_repository: Arc<OrganizationRepository>,
+    organization_member_repository: Arc<OrganizationMemberRepository>,
+    user_repository: Arc<UserRepository>,
+}
+
+impl ExcuseService {
+    pub fn new(
+        excuse_request_repository: Arc<ExcuseRequestRepository>,
+        organization_repository: Arc<OrganizationRepository>,
+        organization_member_repository: Arc<OrganizationMemberRepository>,
+        user_repository: Arc<UserRepository>,
+    ) -> Self {
+        Self {
+            excuse_request_repository,
+            organization_repository,
+            organization_member_repository,
+            user_repository,
+        }
+    }
+
+    async fn find_organization(&self, organization_id: &ObjectId) -> Result<Organization> {
+        self.organization_repository
+            .find_organization_by_id(organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(ExcuseServiceError::OrganizationNotFound))
+    }
+
+    async fn find_user_id(&self, email: &str, missing: ExcuseServiceError) -> Result<ObjectId> {
+        self.user_repository
+            .find_user("email", email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(missing))
+    }
+
+    pub async fn submit_excuse(
+        &self,
+        user_email: &str,
+        request: SubmitExcuseRequest,
+    ) -> Result<ExcuseRequest> {
+        let organization_id = ObjectId::parse_str(&request.organization_id)
+            .map_err(|_| anyhow!(ExcuseServiceError::OrganizationNotFound))?;
+        self.find_organization(&organization_id).await?;
+
+        let user_id = self
+            .find_user_id(user_email, ExcuseServiceError::NotMember)
+            .await?;
+        let member_id = self
+            .organization_member_repository
+            .find_member_by_user(&organization_id, &user_id)
+            .await?
+            .and_then(|member| member._id)
+            .ok_or_else(|| anyhow!(ExcuseServiceError::NotMember))?;
+
+        let excuse = ExcuseRequest {
+            _id: None,
+            organization_id,
+            member_id,
+            date: request.date,
+            reason: request.reason.trim().to_string(),
+            status: ExcuseStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+            created_at: Utc::now(),
+        };
+
+        Ok(self
+            .excuse_request_repository
+            .insert_request(&excuse)
+            .await?)
+    }
+
+    /// Approving also marks the member's attendance for that day as `Excused`.
+    pub async fn review_excuse(
+        &self,
+        excuse_id: &str,
+        reviewer_email: &str,
+        approve: bool,
+    ) -> Result<ExcuseRequest> {
+        let excuse_id = ObjectId::parse_str(excuse_id)
+            .map_err(|_| anyhow!(ExcuseServiceError::ExcuseNotFound))?;
+        let excuse = self
+            .excuse_request_repository
+            .find_request_by_id(&excuse_id)
+            .await?
+            .ok_or_else(|| anyhow!(ExcuseServiceError::ExcuseNotFound))?;
+
+        if excuse.status != ExcuseStatus::Pending {
+            return Err(anyhow!(ExcuseServiceError::AlreadyProcessed));
+        }
+
+        let organization = self.find_organization(&excuse.organization_id).await?;
+        let reviewer_id = self
+            .find_user_id(reviewer_email, ExcuseServiceError::Forbidden)
+            .await?;
+
+        let is_owner = reviewer_id == organization.owner_id;
+        let is_admin = self
+            .organization_member_repository
+            .find_member_by_user(&excuse.organization_id, &reviewer_id)
+            .await?
+            .is_some_and(|member| member.role == Role::Admin);
+        if !is_owner && !is_admin {
+            return Err(anyhow!(ExcuseServiceError::Forbidden));
+        }
+
+        let reviewed = if approve {
+            let (day_start, day_end) = local_date_bounds(organization.tz(), excuse.date);
+            self.excuse_request_repository
+                .approve_request(&excuse_id, &reviewer_id, day_start, day_end)
+                .await?
+        } else {
+            self.excuse_request_repository
+                .reject_request(&excuse_id, &reviewer_id)
+                .await?
+        };
+
+        reviewed.ok_or_else(|| anyhow!(ExcuseServiceError::AlreadyProcessed))
+    }
+}
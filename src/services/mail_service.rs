@@ -0,0 +1,136 @@
+use crate::{constants::MAIL_WEBHOOK_URL, utils::auth_utils::email_verification_required};
+use anyhow::Result;
+use async_trait::async_trait;
+use log::info;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Arc;
+
+/// What a message carries a token for. The receiving end picks the template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MailKind {
+    EmailVerification,
+    PasswordReset,
+}
+
+#[async_trait]
+pub trait MailServiceTrait: Send + Sync {
+    async fn send(&self, email: &str, kind: MailKind, token: &str) -> Result<()>;
+}
+
+/// Writes messages to the log instead of sending them. Only used in debug
+/// builds, since the tokens end up in plain text.
+pub struct LogMailService;
+
+#[async_trait]
+impl MailServiceTrait for LogMailService {
+    async fn send(&self, email: &str, kind: MailKind, token: &str) -> Result<()> {
+        info!("📧 {:?} for {}: {}", kind, email, token);
+        Ok(())
+    }
+}
+
+/// Posts each message as JSON to a relay that renders and sends the email.
+pub struct WebhookMailService {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookMailService {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MailServiceTrait for WebhookMailService {
+    async fn send(&self, email: &str, kind: MailKind, token: &str) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(&json!({ "to": email, "kind": kind, "token": token }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// The configured delivery, or `None` when mail cannot be sent. Fails when
+/// `REQUIRE_EMAIL_VERIFICATION` is on without a way to deliver the links,
+/// since nobody could ever verify and log in.
+pub fn mail_service_from_env() -> Result<Option<Arc<dyn MailServiceTrait>>, String> {
+    select_mail_service(
+        &MAIL_WEBHOOK_URL,
+        cfg!(debug_assertions),
+        email_verification_required(),
+    )
+}
+
+fn select_mail_service(
+    webhook_url: &str,
+    debug_build: bool,
+    verification_required: bool,
+) -> Result<Option<Arc<dyn MailServiceTrait>>, String> {
+    let webhook_url = webhook_url.trim();
+    if !webhook_url.is_empty() {
+        return Ok(Some(Arc::new(WebhookMailService::new(webhook_url))));
+    }
+    if debug_build {
+        return Ok(Some(Arc::new(LogMailService)));
+    }
+    if verification_required {
+        return Err(
+            "REQUIRE_EMAIL_VERIFICATION needs MAIL_WEBHOOK_URL to deliver verification links"
+                .to_string(),
+        );
+    }
+    Ok(None)
+}
+
+/// Keeps every message in memory so tests can read the delivered tokens.
+#[cfg(test)]
+#[derive(Default)]
+pub struct RecordingMailService {
+    pub sent: std::sync::Mutex<Vec<(String, MailKind, String)>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl MailServiceTrait for RecordingMailService {
+    async fn send(&self, email: &str, kind: MailKind, token: &str) -> Result<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((email.to_string(), kind, token.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_webhook_url_enables_delivery_in_any_build() {
+        assert!(
+            select_mail_service("https://mail.example.com/send", false, true)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn debug_builds_fall_back_to_the_log() {
+        assert!(select_mail_service("", true, true).unwrap().is_some());
+    }
+
+    #[test]
+    fn release_builds_without_delivery_refuse_the_verification_gate() {
+        assert!(select_mail_service(" ", false, true).is_err());
+        assert!(select_mail_service("", false, false).unwrap().is_none());
+    }
+}
@@ -1,3 +1,8 @@
+pub mod attendance_service;
+pub mod audit_service;
+pub mod excuse_service;
+pub mod mail_service;
 pub mod organization_member_service;
 pub mod organization_service;
+pub mod password_reset_service;
 pub mod user_service;
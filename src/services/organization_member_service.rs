@@ -1,12 +1,539 @@
-use crate::repositories::user_repository::UserRepository;
-use std::sync::Arc;
+use crate::{
+    config::database::is_duplicate_key_error,
+    models::{organization_member_model::OrganizationMember, organization_model::Organization},
+    repositories::{
+        organization_member_repository::{OrganizationMemberRepository, RoleChange},
+        organization_repository::OrganizationRepository,
+        user_repository::{UserRepository, UserRepositoryTrait},
+    },
+    types::{
+        models::user::role::Role, requests::member::invite_member_request::InviteMemberRequest,
+        responses::member_import_response::MemberImportResponse,
+    },
+    utils::{locale_utils::Messages, validation_utils::validate_member_identifiers},
+    validations::name::validate_name,
+};
+use anyhow::{Result, anyhow};
+use bson::oid::ObjectId;
+use chrono::Utc;
+use csv::{ReaderBuilder, StringRecord, Trim};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
+
+#[derive(Debug)]
+pub enum OrganizationMemberServiceError {
+    OrganizationNotFound,
+    Forbidden,
+    MemberLimitReached,
+    MemberNotFound,
+    LastAdmin,
+    DuplicateIdentifier,
+    AlreadyMember,
+}
+
+impl fmt::Display for OrganizationMemberServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrganizationMemberServiceError::OrganizationNotFound => {
+                write!(f, "Organization not found")
+            }
+            OrganizationMemberServiceError::Forbidden => {
+                write!(f, "Not allowed to manage this organization's members")
+            }
+            OrganizationMemberServiceError::MemberLimitReached => {
+                write!(f, "Organization member limit reached")
+            }
+            OrganizationMemberServiceError::MemberNotFound => write!(f, "Member not found"),
+            OrganizationMemberServiceError::LastAdmin => {
+                write!(f, "Cannot demote the last admin of an organization")
+            }
+            OrganizationMemberServiceError::DuplicateIdentifier => {
+                write!(f, "Member identifier already in use")
+            }
+            OrganizationMemberServiceError::AlreadyMember => {
+                write!(f, "User is already a member of this organization")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrganizationMemberServiceError {}
 
 pub struct OrganizationMemberService {
-    pub user_repository: Arc<UserRepository>,
+    organization_member_repository: Arc<OrganizationMemberRepository>,
+    organization_repository: Arc<OrganizationRepository>,
+    user_repository: Arc<UserRepository>,
 }
 
 impl OrganizationMemberService {
-    pub fn new(user_repository: Arc<UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        organization_member_repository: Arc<OrganizationMemberRepository>,
+        organization_repository: Arc<OrganizationRepository>,
+        user_repository: Arc<UserRepository>,
+    ) -> Self {
+        Self {
+            organization_member_repository,
+            organization_repository,
+            user_repository,
+        }
+    }
+
+    /// Loads the organization and checks that `manager_email` belongs to its
+    /// owner or to one of its admins.
+    async fn find_managed_organization(
+        &self,
+        org_id: &str,
+        manager_email: &str,
+    ) -> Result<Organization> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::OrganizationNotFound))?;
+
+        let manager_id = self
+            .user_repository
+            .find_user("email", manager_email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::Forbidden))?;
+
+        let is_manager = manager_id == organization.owner_id
+            || self
+                .organization_member_repository
+                .has_role(&organization_id, &manager_id, &[Role::Admin])
+                .await?;
+        if !is_manager {
+            return Err(anyhow!(OrganizationMemberServiceError::Forbidden));
+        }
+
+        Ok(organization)
+    }
+
+    /// Lists the members to the owner and to anyone who is a member.
+    pub async fn get_members(
+        &self,
+        org_id: &str,
+        viewer_email: &str,
+    ) -> Result<Vec<OrganizationMember>> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::OrganizationNotFound))?;
+
+        let viewer_id = self
+            .user_repository
+            .find_user("email", viewer_email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::Forbidden))?;
+        let is_member = viewer_id == organization.owner_id
+            || self
+                .organization_member_repository
+                .find_member_by_user(&organization_id, &viewer_id)
+                .await?
+                .is_some();
+        if !is_member {
+            return Err(anyhow!(OrganizationMemberServiceError::Forbidden));
+        }
+
+        Ok(self
+            .organization_member_repository
+            .find_members_by_organization(&organization_id)
+            .await?)
+    }
+
+    pub async fn invite_member(
+        &self,
+        org_id: &str,
+        inviter_email: &str,
+        request: InviteMemberRequest,
+    ) -> Result<OrganizationMember> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .find_managed_organization(org_id, inviter_email)
+            .await?;
+
+        let member = OrganizationMember {
+            _id: None,
+            organization_id,
+            user_id: None,
+            name: request.name.trim().to_string(),
+            role: request.role,
+            identifiers: request.identifiers,
+            joined_at: Utc::now(),
+        };
+
+        self.organization_member_repository
+            .add_member(&member, organization.limits.max_users)
+            .await
+            .map_err(|err| {
+                if is_duplicate_key_error(&err) {
+                    anyhow!(OrganizationMemberServiceError::DuplicateIdentifier)
+                } else {
+                    anyhow!(err)
+                }
+            })?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::MemberLimitReached))
+    }
+
+    pub async fn join_organization(
+        &self,
+        org_id: &str,
+        user_email: &str,
+    ) -> Result<OrganizationMember> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::OrganizationNotFound))?;
+
+        let user = self
+            .user_repository
+            .find_user("email", user_email)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::Forbidden))?;
+        let user_id = user
+            ._id
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::Forbidden))?;
+
+        if self
+            .organization_member_repository
+            .find_member_by_user(&organization_id, &user_id)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!(OrganizationMemberServiceError::AlreadyMember));
+        }
+
+        let member = OrganizationMember {
+            _id: None,
+            organization_id,
+            user_id: Some(user_id),
+            name: user.name,
+            role: Role::User,
+            identifiers: HashMap::new(),
+            joined_at: Utc::now(),
+        };
+
+        self.organization_member_repository
+            .add_member(&member, organization.limits.max_users)
+            .await
+            .map_err(|err| {
+                if is_duplicate_key_error(&err) {
+                    anyhow!(OrganizationMemberServiceError::AlreadyMember)
+                } else {
+                    anyhow!(err)
+                }
+            })?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::MemberLimitReached))
+    }
+
+    pub async fn leave_organization(&self, org_id: &str, user_email: &str) -> Result<()> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        self.organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::OrganizationNotFound))?;
+
+        let user_id = self
+            .user_repository
+            .find_user("email", user_email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::Forbidden))?;
+
+        let member = self
+            .organization_member_repository
+            .find_member_by_user(&organization_id, &user_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationMemberServiceError::MemberNotFound))?;
+
+        if member.role == Role::Admin {
+            let admins = self
+                .organization_member_repository
+                .count_members_with_role(&organization_id, &Role::Admin)
+                .await?;
+            if admins <= 1 {
+                return Err(anyhow!(OrganizationMemberServiceError::LastAdmin));
+            }
+        }
+
+        if !self
+            .organization_member_repository
+            .leave_organization(&organization_id, &user_id)
+            .await?
+        {
+            return Err(anyhow!(OrganizationMemberServiceError::MemberNotFound));
+        }
+
+        Ok(())
+    }
+
+    /// The owner and the organization's admins may change roles, as long as
+    /// at least one admin remains.
+    pub async fn update_member_role(
+        &self,
+        org_id: &str,
+        member_id: &str,
+        manager_email: &str,
+        role: Role,
+    ) -> Result<OrganizationMember> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let member_id = ObjectId::parse_str(member_id)
+            .map_err(|_| anyhow!(OrganizationMemberServiceError::MemberNotFound))?;
+        self.find_managed_organization(org_id, manager_email)
+            .await?;
+
+        match self
+            .organization_member_repository
+            .update_member_role(&organization_id, &member_id, &role)
+            .await?
+        {
+            RoleChange::Updated(member) => Ok(member),
+            RoleChange::MemberNotFound => {
+                Err(anyhow!(OrganizationMemberServiceError::MemberNotFound))
+            }
+            RoleChange::LastAdmin => Err(anyhow!(OrganizationMemberServiceError::LastAdmin)),
+        }
+    }
+
+    pub async fn import_members(
+        &self,
+        org_id: &str,
+        manager_email: &str,
+        csv_data: &str,
+        messages: &Messages,
+    ) -> Result<MemberImportResponse> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .find_managed_organization(org_id, manager_email)
+            .await?;
+
+        let existing = self
+            .organization_member_repository
+            .count_members(&organization_id)
+            .await?;
+        let mut remaining = u64::from(organization.limits.max_users).saturating_sub(existing);
+
+        let mut report = MemberImportResponse::default();
+        let mut accepted = Vec::new();
+        let mut members = Vec::new();
+        let mut seen = HashSet::new();
+
+        let mut reader = ReaderBuilder::new()
+            .trim(Trim::All)
+            .flexible(true)
+            .from_reader(csv_data.as_bytes());
+
+        for (index, record) in reader.records().enumerate() {
+            let fallback_line = index + 2;
+
+            let record = match record {
+                Ok(record) => record,
+                Err(err) => {
+                    let line = err
+                        .position()
+                        .map(|position| position.line() as usize)
+                        .unwrap_or(fallback_line);
+                    report.push_failure(
+                        line,
+                        messages.get_member_message("import.invalid_row", "Malformed CSV row"),
+                    );
+                    continue;
+                }
+            };
+
+            let line = record
+                .position()
+                .map(|position| position.line() as usize)
+                .unwrap_or(fallback_line);
+
+            let member = match parse_member_row(&record, organization_id, messages) {
+                Ok(member) => member,
+                Err(error) => {
+                    report.push_failure(line, error);
+                    continue;
+                }
+            };
+
+            let keys = row_keys(&member);
+            if keys.iter().any(|key| seen.contains(key)) {
+                report.push_failure(
+                    line,
+                    messages.get_member_message(
+                        "import.duplicate_row",
+                        "This member already appears earlier in the file",
+                    ),
+                );
+                continue;
+            }
+
+            if remaining == 0 {
+                report.push_failure(
+                    line,
+                    messages.get_member_message(
+                        "import.limit_reached",
+                        "The organization member limit has been reached",
+                    ),
+                );
+                continue;
+            }
+
+            remaining -= 1;
+            seen.extend(keys);
+            accepted.push(line);
+            members.push(member);
+        }
+
+        let rejected = self
+            .organization_member_repository
+            .insert_members(&organization_id, &members)
+            .await?;
+
+        for (index, line) in accepted.into_iter().enumerate() {
+            if rejected.contains(&index) {
+                report.push_failure(
+                    line,
+                    messages.get_member_message(
+                        "invite.duplicate_identifier",
+                        "Another member of this organization already uses that identifier.",
+                    ),
+                );
+            } else {
+                report.push_success(line);
+            }
+        }
+        report.rows.sort_by_key(|row| row.line);
+
+        Ok(report)
+    }
+}
+
+/// What makes two rows of one file the same member: any shared identifier,
+/// or the name when a row has no identifiers at all.
+fn row_keys(member: &OrganizationMember) -> Vec<String> {
+    if member.identifiers.is_empty() {
+        return vec![format!("name:{}", member.name.to_lowercase())];
+    }
+    member
+        .identifiers
+        .iter()
+        .map(|(kind, value)| format!("{kind}={value}"))
+        .collect()
+}
+
+fn parse_member_row(
+    record: &StringRecord,
+    organization_id: ObjectId,
+    messages: &Messages,
+) -> Result<OrganizationMember, String> {
+    let name = record.get(0).unwrap_or_default();
+    if let Err(errors) = validate_name(name, messages) {
+        let reasons: Vec<String> = errors
+            .into_iter()
+            .filter_map(|error| error.message.map(|message| message.to_string()))
+            .collect();
+        return Err(if reasons.is_empty() {
+            messages.get_validation_message("name.invalid", "Invalid name")
+        } else {
+            reasons.join(", ")
+        });
+    }
+
+    let role = match record.get(1).filter(|role| !role.is_empty()) {
+        Some(role) => role.parse::<Role>().map_err(|_| {
+            messages.get_member_message("import.invalid_role", "Role must be admin, staff or user")
+        })?,
+        None => Role::User,
+    };
+
+    let identifiers = parse_identifiers(record.get(2).unwrap_or_default()).ok_or_else(|| {
+        messages.get_member_message(
+            "import.invalid_identifiers",
+            "Identifiers must be written as key=value pairs separated by ';'",
+        )
+    })?;
+    if let Err(errs) = validate_member_identifiers(&identifiers, messages) {
+        return Err(errs
+            .field_errors()
+            .values()
+            .flat_map(|errors| errors.iter())
+            .find_map(|error| error.message.as_ref().map(|message| message.to_string()))
+            .unwrap_or_else(|| {
+                messages.get_member_message(
+                    "import.invalid_identifiers",
+                    "Identifiers must be written as key=value pairs separated by ';'",
+                )
+            }));
+    }
+
+    Ok(OrganizationMember {
+        _id: None,
+        organization_id,
+        user_id: None,
+        name: name.to_string(),
+        role,
+        identifiers,
+        joined_at: Utc::now(),
+    })
+}
+
+fn parse_identifiers(raw: &str) -> Option<HashMap<String, String>> {
+    raw.split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let (key, value) = (key.trim(), value.trim());
+            (!key.is_empty() && !value.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, identifiers: &[(&str, &str)]) -> OrganizationMember {
+        OrganizationMember {
+            _id: None,
+            organization_id: ObjectId::new(),
+            user_id: None,
+            name: name.to_string(),
+            role: Role::User,
+            identifiers: identifiers
+                .iter()
+                .map(|(kind, value)| (kind.to_string(), value.to_string()))
+                .collect(),
+            joined_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rows_sharing_an_identifier_have_a_common_key() {
+        let first = row_keys(&member("Jane", &[("nim", "123"), ("nip", "9")]));
+        let second = row_keys(&member("John", &[("nim", "123")]));
+
+        assert!(second.iter().any(|key| first.contains(key)));
+    }
+
+    #[test]
+    fn rows_without_identifiers_are_keyed_by_name() {
+        assert_eq!(
+            row_keys(&member("Jane Doe", &[])),
+            row_keys(&member("jane doe", &[]))
+        );
+        assert_ne!(
+            row_keys(&member("Jane Doe", &[])),
+            row_keys(&member("John Doe", &[]))
+        );
     }
 }
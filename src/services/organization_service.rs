@@ -1,38 +1,351 @@
 use crate::{
-    models::organization_model::Organization,
-    repositories::organization_repository::OrganizationRepository,
+    models::{
+        audit_log_model::AuditLog, idempotency_key_model::IdempotencyKey,
+        organization_model::Organization, user_model::User,
+    },
+    repositories::{
+        idempotency_repository::IdempotencyRepository,
+        organization_member_repository::OrganizationMemberRepository,
+        organization_repository::OrganizationRepository,
+        user_repository::{UserRepository, UserRepositoryTrait},
+    },
+    services::audit_service::AuditService,
+    types::{
+        models::{
+            audit::{audit_action::AuditAction, audit_target::AuditTarget},
+            user::{role::Role, subscription::SubscriptionPlan},
+        },
+        requests::{
+            common::pagination_query::PaginationQuery,
+            organization::{
+                create_organization_request::CreateOrganizationRequest,
+                update_organization_request::UpdateOrganizationRequest,
+            },
+        },
+        responses::api_response::PaginatedResponse,
+    },
+    utils::auth_utils::normalize_email,
 };
-use anyhow::Result;
-use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use bson::{DateTime, Document, oid::ObjectId};
+use log::warn;
+use std::{fmt, sync::Arc};
+
+const CREATE_ORGANIZATION_SCOPE: &str = "organization.create";
+
+#[derive(Debug)]
+pub enum OrganizationServiceError {
+    OwnerNotFound,
+    OrganizationLimitReached,
+    OrganizationNotFound,
+    Forbidden,
+    LimitsExceeded,
+    PlanLimitsExceeded,
+    NewOwnerNotFound,
+    NewOwnerNotMember,
+    VersionConflict,
+    RequestInProgress,
+}
+
+impl fmt::Display for OrganizationServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrganizationServiceError::OwnerNotFound => write!(f, "Organization owner not found"),
+            OrganizationServiceError::OrganizationLimitReached => {
+                write!(
+                    f,
+                    "Organization limit reached for the owner's subscription plan"
+                )
+            }
+            OrganizationServiceError::OrganizationNotFound => write!(f, "Organization not found"),
+            OrganizationServiceError::Forbidden => {
+                write!(f, "Not allowed to access this organization")
+            }
+            OrganizationServiceError::LimitsExceeded => {
+                write!(
+                    f,
+                    "Organization limits exceed the owner's subscription plan"
+                )
+            }
+            OrganizationServiceError::PlanLimitsExceeded => {
+                write!(f, "Owned organizations exceed the target subscription plan")
+            }
+            OrganizationServiceError::NewOwnerNotFound => write!(f, "New owner not found"),
+            OrganizationServiceError::NewOwnerNotMember => {
+                write!(f, "New owner is not a member of the organization")
+            }
+            OrganizationServiceError::VersionConflict => {
+                write!(f, "Organization was modified concurrently")
+            }
+            OrganizationServiceError::RequestInProgress => {
+                write!(
+                    f,
+                    "A request with this idempotency key is still in progress"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrganizationServiceError {}
 
 pub struct OrganizationService {
     organization_repository: Arc<OrganizationRepository>,
+    organization_member_repository: Arc<OrganizationMemberRepository>,
+    user_repository: Arc<UserRepository>,
+    idempotency_repository: Arc<IdempotencyRepository>,
+    audit_service: Arc<AuditService>,
 }
 
 impl OrganizationService {
-    pub fn new(organization_repository: Arc<OrganizationRepository>) -> Self {
+    pub fn new(
+        organization_repository: Arc<OrganizationRepository>,
+        organization_member_repository: Arc<OrganizationMemberRepository>,
+        user_repository: Arc<UserRepository>,
+        idempotency_repository: Arc<IdempotencyRepository>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
         Self {
             organization_repository,
+            organization_member_repository,
+            user_repository,
+            idempotency_repository,
+            audit_service,
         }
     }
 
-    pub async fn create_organization(&self, organization: Organization) -> Result<Organization> {
-        self.organization_repository
+    /// Loads the organization and checks that `email` belongs to its owner or
+    /// to one of its admins.
+    async fn find_managed_organization(
+        &self,
+        org_id: &str,
+        email: &str,
+    ) -> Result<(ObjectId, Organization)> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OrganizationNotFound))?;
+
+        let user_id = self
+            .user_repository
+            .find_user("email", email)
+            .await?
+            .and_then(|user| user._id)
+            .ok_or_else(|| anyhow!(OrganizationServiceError::Forbidden))?;
+
+        let is_manager = user_id == organization.owner_id
+            || self
+                .organization_member_repository
+                .has_role(&organization_id, &user_id, &[Role::Admin])
+                .await?;
+        if !is_manager {
+            return Err(anyhow!(OrganizationServiceError::Forbidden));
+        }
+
+        Ok((organization_id, organization))
+    }
+
+    /// Creates an organization owned by the account behind `owner_email`, who
+    /// is also recorded as the actor.
+    pub async fn create_organization_idempotent(
+        &self,
+        request: CreateOrganizationRequest,
+        owner_email: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<Organization> {
+        let owner = self
+            .user_repository
+            .find_user("email", owner_email)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OwnerNotFound))?;
+        let owner_id = owner
+            ._id
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OwnerNotFound))?;
+        let organization = request.into_organization(owner_id);
+
+        let Some(key) = idempotency_key else {
+            return self.create_organization(organization, &owner).await;
+        };
+
+        // The owner is the authenticated caller, so keys are scoped to them
+        // and one client can neither replay nor block another's request.
+
+        let reserved = self
+            .idempotency_repository
+            .reserve_key(&IdempotencyKey {
+                _id: None,
+                key: key.to_string(),
+                scope: CREATE_ORGANIZATION_SCOPE.to_string(),
+                user_id: owner_id,
+                response: None,
+                created_at: DateTime::now(),
+            })
+            .await?;
+        if !reserved {
+            let stored = self
+                .idempotency_repository
+                .find_key(&owner_id, CREATE_ORGANIZATION_SCOPE, key)
+                .await?;
+            return match stored.and_then(|stored| stored.response) {
+                Some(response) => Ok(bson::from_document(response)?),
+                None => Err(anyhow!(OrganizationServiceError::RequestInProgress)),
+            };
+        }
+
+        let created = match self.create_organization(organization, &owner).await {
+            Ok(created) => created,
+            Err(err) => {
+                if let Err(release_err) = self
+                    .idempotency_repository
+                    .release_key(&owner_id, CREATE_ORGANIZATION_SCOPE, key)
+                    .await
+                {
+                    warn!("⚠️ Failed to release idempotency key: {:?}", release_err);
+                }
+                return Err(err);
+            }
+        };
+
+        self.idempotency_repository
+            .complete_key(
+                &owner_id,
+                CREATE_ORGANIZATION_SCOPE,
+                key,
+                bson::to_document(&created)?,
+            )
+            .await?;
+
+        Ok(created)
+    }
+
+    async fn create_organization(
+        &self,
+        organization: Organization,
+        owner: &User,
+    ) -> Result<Organization> {
+        let owner_id = organization.owner_id;
+
+        if !organization
+            .limits
+            .fits_within(&owner.subscription_plan.default_limits())
+        {
+            return Err(anyhow!(OrganizationServiceError::LimitsExceeded));
+        }
+
+        let reserved = self
+            .user_repository
+            .reserve_organization_slot(&owner_id, &owner.subscription_plan)
+            .await?;
+
+        if !reserved {
+            return Err(anyhow!(OrganizationServiceError::OrganizationLimitReached));
+        }
+
+        match self
+            .organization_repository
             .create_organization(organization)
             .await
-            .map_err(anyhow::Error::from)
+        {
+            Ok(created) => {
+                if let Some(organization_id) = created._id {
+                    self.audit_service.record(AuditLog::for_organization(
+                        organization_id,
+                        Some(&owner.email),
+                        AuditAction::Create,
+                    ));
+                }
+                Ok(created)
+            }
+            Err(err) => {
+                self.user_repository
+                    .release_organization_slot(&owner_id)
+                    .await?;
+                Err(anyhow::Error::from(err))
+            }
+        }
+    }
+
+    /// Moves the owner to `plan`, refusing a downgrade that the organizations
+    /// they already own would not fit into.
+    pub async fn change_subscription_plan(
+        &self,
+        owner_email: &str,
+        plan: SubscriptionPlan,
+        actor: Option<&str>,
+    ) -> Result<User> {
+        let owner_email = &normalize_email(owner_email);
+        let owner_id = self
+            .user_repository
+            .find_user("email", owner_email)
+            .await?
+            .and_then(|owner| owner._id)
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OwnerNotFound))?;
+
+        let owned = self
+            .organization_repository
+            .find_organizations_by_owner(&owner_id)
+            .await?;
+        let ceiling = plan.default_limits();
+        if owned
+            .iter()
+            .any(|organization| !organization.limits.fits_within(&ceiling))
+        {
+            return Err(anyhow!(OrganizationServiceError::PlanLimitsExceeded));
+        }
+
+        // The organization count is checked by the update itself, so an
+        // organization created meanwhile cannot slip past the new limit.
+        let updated = self
+            .user_repository
+            .update_subscription_plan(owner_email, &plan)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::PlanLimitsExceeded))?;
+
+        self.audit_service.record(AuditLog::new(
+            actor,
+            AuditAction::Update,
+            AuditTarget::User,
+            owner_email,
+        ));
+
+        Ok(updated)
     }
 
     pub async fn get_organization_by_id(&self, org_id: &str) -> Result<Option<Organization>> {
+        let organization_id = ObjectId::parse_str(org_id)?;
         self.organization_repository
-            .find_organization_by_id(org_id)
+            .find_organization_by_id(&organization_id)
             .await
             .map_err(anyhow::Error::from)
     }
 
-    pub async fn get_all_organizations(&self) -> Result<Vec<Organization>> {
+    pub async fn get_all_organizations(
+        &self,
+        filter: Document,
+        pagination: &PaginationQuery,
+    ) -> Result<PaginatedResponse<Organization>> {
+        let organizations = self
+            .organization_repository
+            .find_organizations(filter.clone(), pagination.skip(), pagination.per_page())
+            .await?;
+        let total = self
+            .organization_repository
+            .count_organizations(filter)
+            .await?;
+
+        Ok(PaginatedResponse::new(
+            organizations,
+            total,
+            pagination.page(),
+            pagination.per_page(),
+        ))
+    }
+
+    pub async fn count_organizations(&self, filter: Document) -> Result<u64> {
         self.organization_repository
-            .get_all_organizations()
+            .count_organizations(filter)
             .await
             .map_err(anyhow::Error::from)
     }
@@ -40,18 +353,176 @@ impl OrganizationService {
     pub async fn update_organization(
         &self,
         org_id: &str,
-        organization: Organization,
+        update: UpdateOrganizationRequest,
+        actor: &str,
     ) -> Result<Organization> {
-        self.organization_repository
-            .update_organization(org_id, &organization)
+        let (organization_id, existing) = self.find_managed_organization(org_id, actor).await?;
+
+        if let Some(limits) = &update.limits {
+            let owner = self
+                .user_repository
+                .find_user_by_id(&existing.owner_id)
+                .await?
+                .ok_or_else(|| anyhow!(OrganizationServiceError::OwnerNotFound))?;
+
+            if !limits.fits_within(&owner.subscription_plan.default_limits()) {
+                return Err(anyhow!(OrganizationServiceError::LimitsExceeded));
+            }
+        }
+
+        let updated = self
+            .organization_repository
+            .update_organization(&organization_id, &update)
+            .await?
+            .ok_or_else(|| match update.version {
+                Some(_) => anyhow!(OrganizationServiceError::VersionConflict),
+                None => anyhow!(OrganizationServiceError::OrganizationNotFound),
+            })?;
+
+        self.audit_service.record(AuditLog::for_organization(
+            organization_id,
+            Some(actor),
+            AuditAction::Update,
+        ));
+
+        Ok(updated)
+    }
+
+    /// Only the owner may delete an organization.
+    pub async fn delete_organization(&self, org_id: &str, actor: &str) -> Result<()> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OrganizationNotFound))?;
+
+        let requester_id = self
+            .user_repository
+            .find_user("email", actor)
+            .await?
+            .and_then(|user| user._id);
+        if requester_id != Some(organization.owner_id) {
+            return Err(anyhow!(OrganizationServiceError::Forbidden));
+        }
+
+        let deleted = self
+            .organization_repository
+            .delete_organization(&organization_id)
+            .await?;
+        if deleted == 0 {
+            return Err(anyhow!(OrganizationServiceError::OrganizationNotFound));
+        }
+
+        self.user_repository
+            .release_organization_slot(&organization.owner_id)
+            .await?;
+
+        self.audit_service.record(AuditLog::for_organization(
+            organization_id,
+            Some(actor),
+            AuditAction::Delete,
+        ));
+
+        Ok(())
+    }
+
+    /// Hands the organization to one of its members. Only the current owner may
+    /// do this, and the organization must fit the new owner's plan.
+    pub async fn transfer_ownership(
+        &self,
+        org_id: &str,
+        new_owner_id: &ObjectId,
+        requester_email: &str,
+    ) -> Result<Organization> {
+        let organization_id = ObjectId::parse_str(org_id)?;
+        let organization = self
+            .organization_repository
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::OrganizationNotFound))?;
+        let current_owner_id = organization.owner_id;
+
+        let requester = self
+            .user_repository
+            .find_user("email", requester_email)
+            .await?;
+        if requester.and_then(|user| user._id) != Some(current_owner_id) {
+            return Err(anyhow!(OrganizationServiceError::Forbidden));
+        }
+
+        if *new_owner_id == current_owner_id {
+            return Ok(organization);
+        }
+
+        let new_owner = self
+            .user_repository
+            .find_user_by_id(new_owner_id)
+            .await?
+            .ok_or_else(|| anyhow!(OrganizationServiceError::NewOwnerNotFound))?;
+        if !new_owner.organization_ids.contains(&organization_id) {
+            return Err(anyhow!(OrganizationServiceError::NewOwnerNotMember));
+        }
+        if !organization
+            .limits
+            .fits_within(&new_owner.subscription_plan.default_limits())
+        {
+            return Err(anyhow!(OrganizationServiceError::LimitsExceeded));
+        }
+
+        let reserved = self
+            .user_repository
+            .reserve_organization_slot(new_owner_id, &new_owner.subscription_plan)
+            .await?;
+        if !reserved {
+            return Err(anyhow!(OrganizationServiceError::OrganizationLimitReached));
+        }
+
+        let transferred = match self
+            .organization_repository
+            .transfer_ownership(&organization_id, &current_owner_id, new_owner_id)
             .await
-            .map_err(anyhow::Error::from)
+        {
+            Ok(Some(transferred)) => transferred,
+            Ok(None) => {
+                self.user_repository
+                    .release_organization_slot(new_owner_id)
+                    .await?;
+                return Err(anyhow!(OrganizationServiceError::Forbidden));
+            }
+            Err(err) => {
+                self.user_repository
+                    .release_organization_slot(new_owner_id)
+                    .await?;
+                return Err(anyhow::Error::from(err));
+            }
+        };
+
+        self.user_repository
+            .release_organization_slot(&current_owner_id)
+            .await?;
+
+        self.audit_service.record(AuditLog::for_organization(
+            organization_id,
+            Some(requester_email),
+            AuditAction::Update,
+        ));
+
+        Ok(transferred)
     }
 
-    pub async fn delete_organization(&self, org_id: &str) -> Result<()> {
-        self.organization_repository
-            .delete_organization(org_id)
+    pub async fn get_audit_logs(
+        &self,
+        org_id: &str,
+        requester_email: &str,
+        pagination: &PaginationQuery,
+    ) -> Result<PaginatedResponse<AuditLog>> {
+        let (organization_id, _) = self
+            .find_managed_organization(org_id, requester_email)
+            .await?;
+
+        self.audit_service
+            .get_organization_logs(&organization_id, pagination)
             .await
-            .map_err(anyhow::Error::from)
     }
 }
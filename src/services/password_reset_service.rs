@@ -0,0 +1,365 @@
+use crate::{
+    models::password_reset_token_model::PasswordResetToken,
+    repositories::{
+        password_reset_repository::PasswordResetRepositoryTrait,
+        user_repository::UserRepositoryTrait,
+    },
+    services::mail_service::{MailKind, MailServiceTrait},
+    utils::auth_utils::{
+        generate_reset_secret, hash_password, normalize_email, password_reset_expiry,
+        verify_password,
+    },
+};
+use anyhow::{Result, anyhow};
+use bson::{DateTime, oid::ObjectId};
+use log::{error, info};
+use std::{fmt, sync::Arc};
+
+#[derive(Debug)]
+pub enum PasswordResetServiceError {
+    InvalidToken,
+    DeliveryUnavailable,
+}
+
+impl fmt::Display for PasswordResetServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordResetServiceError::InvalidToken => {
+                write!(f, "Password reset token is invalid or expired")
+            }
+            PasswordResetServiceError::DeliveryUnavailable => {
+                write!(f, "No mail service is configured to deliver reset links")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PasswordResetServiceError {}
+
+pub struct PasswordResetService {
+    password_reset_repository: Arc<dyn PasswordResetRepositoryTrait>,
+    user_repository: Arc<dyn UserRepositoryTrait>,
+    mail_service: Option<Arc<dyn MailServiceTrait>>,
+}
+
+impl PasswordResetService {
+    pub fn new(
+        password_reset_repository: Arc<dyn PasswordResetRepositoryTrait>,
+        user_repository: Arc<dyn UserRepositoryTrait>,
+        mail_service: Option<Arc<dyn MailServiceTrait>>,
+    ) -> Self {
+        Self {
+            password_reset_repository,
+            user_repository,
+            mail_service,
+        }
+    }
+
+    /// Issues a reset token for `email` and mails it in the background.
+    /// Unknown emails succeed silently after the same hashing work, so neither
+    /// the response nor its timing reveals which accounts are registered.
+    pub async fn request_reset(&self, email: &str) -> Result<()> {
+        let Some(mail_service) = self.mail_service.clone() else {
+            return Err(anyhow!(PasswordResetServiceError::DeliveryUnavailable));
+        };
+
+        let secret = generate_reset_secret();
+        let token_hash = hash_password(&secret).map_err(|e| anyhow!(e))?;
+
+        let Some(user) = self
+            .user_repository
+            .find_user("email", &normalize_email(email))
+            .await?
+        else {
+            return Ok(());
+        };
+        let Some(user_id) = user._id else {
+            return Ok(());
+        };
+
+        self.password_reset_repository
+            .delete_tokens_for_user(&user_id)
+            .await?;
+
+        let now = DateTime::now();
+
+        let token_id = self
+            .password_reset_repository
+            .insert_token(&PasswordResetToken {
+                _id: None,
+                user_id,
+                token_hash,
+                expires_at: DateTime::from_millis(
+                    now.timestamp_millis() + password_reset_expiry().num_milliseconds(),
+                ),
+                created_at: now,
+            })
+            .await?;
+
+        info!("Password reset token issued for user {}", user_id);
+        let token = format!("{token_id}.{secret}");
+        actix_rt::spawn(async move {
+            if let Err(err) = mail_service
+                .send(&user.email, MailKind::PasswordReset, &token)
+                .await
+            {
+                error!(
+                    "❌ Failed to send password reset email to {}: {:?}",
+                    user.email, err
+                );
+            }
+        });
+
+        Ok(())
+    }
+
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<()> {
+        let (token_id, secret) = token
+            .split_once('.')
+            .and_then(|(id, secret)| Some((ObjectId::parse_str(id).ok()?, secret)))
+            .ok_or_else(|| anyhow!(PasswordResetServiceError::InvalidToken))?;
+
+        let stored = self
+            .password_reset_repository
+            .find_active_token(&token_id)
+            .await?
+            .ok_or_else(|| anyhow!(PasswordResetServiceError::InvalidToken))?;
+
+        if !verify_password(secret, &stored.token_hash).unwrap_or(false) {
+            return Err(anyhow!(PasswordResetServiceError::InvalidToken));
+        }
+
+        if !self
+            .password_reset_repository
+            .consume_token(&token_id)
+            .await?
+        {
+            return Err(anyhow!(PasswordResetServiceError::InvalidToken));
+        }
+
+        let user = self
+            .user_repository
+            .find_user_by_id(&stored.user_id)
+            .await?
+            .ok_or_else(|| anyhow!(PasswordResetServiceError::InvalidToken))?;
+
+        let password_hash = hash_password(new_password).map_err(|e| anyhow!(e))?;
+        self.user_repository
+            .update_password(&user.email, &password_hash)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::in_memory_user_repository::{InMemoryUserRepository, user};
+    use crate::services::mail_service::RecordingMailService;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Keeps tokens in a `Vec`, honoring expiry like the TTL-indexed collection.
+    #[derive(Default)]
+    struct InMemoryResetRepository {
+        tokens: Mutex<Vec<PasswordResetToken>>,
+    }
+
+    #[async_trait]
+    impl PasswordResetRepositoryTrait for InMemoryResetRepository {
+        async fn insert_token(
+            &self,
+            token: &PasswordResetToken,
+        ) -> mongodb::error::Result<ObjectId> {
+            let id = ObjectId::new();
+            let mut token = token.clone();
+            token._id = Some(id);
+            self.tokens.lock().unwrap().push(token);
+            Ok(id)
+        }
+
+        async fn find_active_token(
+            &self,
+            token_id: &ObjectId,
+        ) -> mongodb::error::Result<Option<PasswordResetToken>> {
+            let now = DateTime::now();
+            Ok(self
+                .tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token._id.as_ref() == Some(token_id) && token.expires_at > now)
+                .cloned())
+        }
+
+        async fn consume_token(&self, token_id: &ObjectId) -> mongodb::error::Result<bool> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let before = tokens.len();
+            tokens.retain(|token| token._id.as_ref() != Some(token_id));
+            Ok(tokens.len() < before)
+        }
+
+        async fn delete_tokens_for_user(&self, user_id: &ObjectId) -> mongodb::error::Result<()> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .retain(|token| token.user_id != *user_id);
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        service: PasswordResetService,
+        resets: Arc<InMemoryResetRepository>,
+        users: Arc<InMemoryUserRepository>,
+        mail: Arc<RecordingMailService>,
+        user_id: ObjectId,
+    }
+
+    fn fixture() -> Fixture {
+        let account = user("ana@example.com", "Oldpassword123.");
+        let user_id = account._id.unwrap();
+        let resets = Arc::new(InMemoryResetRepository::default());
+        let users = Arc::new(InMemoryUserRepository::with_users(vec![account]));
+        let mail = Arc::new(RecordingMailService::default());
+        Fixture {
+            service: PasswordResetService::new(resets.clone(), users.clone(), Some(mail.clone())),
+            resets,
+            users,
+            mail,
+            user_id,
+        }
+    }
+
+    /// Stores a token expiring `lifetime_ms` from now and returns what the
+    /// emailed link would carry.
+    async fn issue(fixture: &Fixture, lifetime_ms: i64) -> String {
+        let now = DateTime::now();
+        let id = fixture
+            .resets
+            .insert_token(&PasswordResetToken {
+                _id: None,
+                user_id: fixture.user_id,
+                token_hash: hash_password("secret").unwrap(),
+                expires_at: DateTime::from_millis(now.timestamp_millis() + lifetime_ms),
+                created_at: now,
+            })
+            .await
+            .unwrap();
+        format!("{id}.secret")
+    }
+
+    fn is_invalid_token(err: &anyhow::Error) -> bool {
+        matches!(
+            err.downcast_ref::<PasswordResetServiceError>(),
+            Some(PasswordResetServiceError::InvalidToken)
+        )
+    }
+
+    #[actix_rt::test]
+    async fn a_valid_token_sets_the_new_password() {
+        let fixture = fixture();
+        let token = issue(&fixture, 60_000).await;
+
+        fixture
+            .service
+            .reset_password(&token, "Newpassword123.")
+            .await
+            .unwrap();
+
+        let account = fixture.users.user("ana@example.com").unwrap();
+        assert!(verify_password("Newpassword123.", &account.password).unwrap());
+        assert_eq!(account.token_version, 1);
+    }
+
+    #[actix_rt::test]
+    async fn a_token_cannot_be_used_twice() {
+        let fixture = fixture();
+        let token = issue(&fixture, 60_000).await;
+        fixture
+            .service
+            .reset_password(&token, "Newpassword123.")
+            .await
+            .unwrap();
+
+        let err = fixture
+            .service
+            .reset_password(&token, "Otherpassword123.")
+            .await
+            .unwrap_err();
+
+        assert!(is_invalid_token(&err));
+        let account = fixture.users.user("ana@example.com").unwrap();
+        assert!(verify_password("Newpassword123.", &account.password).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn an_expired_token_is_rejected() {
+        let fixture = fixture();
+        let token = issue(&fixture, -1_000).await;
+
+        let err = fixture
+            .service
+            .reset_password(&token, "Newpassword123.")
+            .await
+            .unwrap_err();
+
+        assert!(is_invalid_token(&err));
+        let account = fixture.users.user("ana@example.com").unwrap();
+        assert!(verify_password("Oldpassword123.", &account.password).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn requests_for_unknown_emails_succeed_without_issuing_a_token() {
+        let fixture = fixture();
+
+        fixture
+            .service
+            .request_reset("nobody@example.com")
+            .await
+            .unwrap();
+
+        assert!(fixture.resets.tokens.lock().unwrap().is_empty());
+        actix_rt::task::yield_now().await;
+        assert!(fixture.mail.sent.lock().unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn the_mailed_token_resets_the_password() {
+        let fixture = fixture();
+
+        fixture
+            .service
+            .request_reset(" ana@EXAMPLE.com")
+            .await
+            .unwrap();
+        actix_rt::task::yield_now().await;
+
+        let (email, kind, token) = fixture.mail.sent.lock().unwrap().pop().expect("a mail");
+        assert_eq!(email, "ana@example.com");
+        assert_eq!(kind, MailKind::PasswordReset);
+        fixture
+            .service
+            .reset_password(&token, "Newpassword123.")
+            .await
+            .unwrap();
+        let account = fixture.users.user("ana@example.com").unwrap();
+        assert!(verify_password("Newpassword123.", &account.password).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn without_a_mail_service_no_token_is_issued() {
+        let fixture = fixture();
+        let service =
+            PasswordResetService::new(fixture.resets.clone(), fixture.users.clone(), None);
+
+        let err = service.request_reset("ana@example.com").await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<PasswordResetServiceError>(),
+            Some(PasswordResetServiceError::DeliveryUnavailable)
+        ));
+        assert!(fixture.resets.tokens.lock().unwrap().is_empty());
+    }
+}
@@ -1,22 +1,36 @@
 use crate::{
-    models::user_model::User,
-    repositories::user_repository::UserRepository,
+    config::database::is_duplicate_key_error,
+    models::{audit_log_model::AuditLog, user_model::User},
+    repositories::user_repository::UserRepositoryTrait,
+    services::{
+        audit_service::AuditService,
+        mail_service::{MailKind, MailServiceTrait},
+    },
     types::{
-        models::user::defaults::default_status,
+        models::{
+            audit::{audit_action::AuditAction, audit_target::AuditTarget},
+            user::{defaults::default_status, user_status::UserStatus},
+        },
         requests::{
-            auth::register_request::RegisterRequest, user::update_user_request::UpdateUserRequest,
+            auth::register_request::RegisterRequest, common::pagination_query::PaginationQuery,
+            user::update_user_request::UpdateUserRequest,
         },
+        responses::{api_error::ApiError, api_response::PaginatedResponse},
     },
     utils::{
-        auth_utils::{generate_jwt, hash_password, verify_password},
+        auth_utils::{
+            email_verification_required, generate_jwt, generate_verification_token, hash_password,
+            login_lockout_duration, login_lockout_threshold, normalize_email,
+            password_needs_rehash, verify_password, verify_verification_token,
+        },
         locale_utils::Messages,
     },
+    validations::identifier::is_nim,
 };
-use anyhow::anyhow;
-use anyhow::{Context, Result};
-use bson::oid::ObjectId;
-use chrono::Utc;
-use std::{collections::HashSet, sync::Arc};
+use bson::{Document, oid::ObjectId};
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use std::{collections::HashSet, fmt, sync::Arc};
 
 #[derive(Debug)]
 pub enum UserServiceError {
@@ -26,10 +40,41 @@ pub enum UserServiceError {
     DbError(String),
     JwtGenerationError(String),
     PasswordHashingError(String),
+    EmailNotVerified,
+    AccountLocked,
+    AccountInactive,
+    VersionConflict,
+}
+
+impl fmt::Display for UserServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserServiceError::NotFound => write!(f, "User not found"),
+            UserServiceError::InvalidCredentials => write!(f, "Invalid credentials"),
+            UserServiceError::DuplicateEmail => write!(f, "Duplicate email"),
+            UserServiceError::DbError(err) => write!(f, "Database error: {err}"),
+            UserServiceError::JwtGenerationError(err) => write!(f, "JWT generation failed: {err}"),
+            UserServiceError::PasswordHashingError(err) => {
+                write!(f, "Password hashing failed: {err}")
+            }
+            UserServiceError::EmailNotVerified => write!(f, "Email not verified"),
+            UserServiceError::AccountLocked => write!(f, "Account locked"),
+            UserServiceError::AccountInactive => write!(f, "Account is not active"),
+            UserServiceError::VersionConflict => write!(f, "User was modified concurrently"),
+        }
+    }
+}
+
+impl std::error::Error for UserServiceError {}
+
+impl From<mongodb::error::Error> for UserServiceError {
+    fn from(err: mongodb::error::Error) -> Self {
+        UserServiceError::DbError(err.to_string())
+    }
 }
 
 impl UserServiceError {
-    fn to_message(&self, messages: &Messages) -> String {
+    pub fn to_message(&self, messages: &Messages) -> String {
         match self {
             UserServiceError::NotFound => {
                 messages.get_user_message("fetch.not_found", "User not found")
@@ -50,77 +95,196 @@ impl UserServiceError {
             UserServiceError::PasswordHashingError(_) => {
                 messages.get_auth_message("auth.password_hashing_failed", "Password hashing failed")
             }
+            UserServiceError::EmailNotVerified => messages.get_auth_message(
+                "login.email_not_verified",
+                "Please verify your email address before logging in.",
+            ),
+            UserServiceError::AccountLocked => messages.get_auth_message(
+                "login.account_locked",
+                "Too many failed login attempts. Please try again later.",
+            ),
+            UserServiceError::AccountInactive => messages.get_auth_message(
+                "login.account_inactive",
+                "This account has been deactivated or suspended.",
+            ),
+            UserServiceError::VersionConflict => messages.get_user_message(
+                "update.conflict",
+                "The user was changed by someone else. Reload and try again.",
+            ),
+        }
+    }
+
+    /// Maps each variant to the status code handlers respond with.
+    pub fn to_api_error(&self, messages: &Messages) -> ApiError {
+        let message = self.to_message(messages);
+        match self {
+            UserServiceError::NotFound => ApiError::NotFound(message),
+            UserServiceError::InvalidCredentials
+            | UserServiceError::EmailNotVerified
+            | UserServiceError::AccountLocked => ApiError::Unauthorized(message),
+            UserServiceError::AccountInactive => ApiError::Forbidden(message),
+            UserServiceError::DuplicateEmail | UserServiceError::VersionConflict => {
+                ApiError::Conflict(message)
+            }
+            UserServiceError::DbError(_)
+            | UserServiceError::JwtGenerationError(_)
+            | UserServiceError::PasswordHashingError(_) => {
+                error!("❌ User service failure: {}", self);
+                ApiError::Internal(message)
+            }
         }
     }
 }
 
 pub struct UserService {
-    pub user_repository: Arc<UserRepository>,
+    pub user_repository: Arc<dyn UserRepositoryTrait>,
+    audit_service: Arc<AuditService>,
+    mail_service: Option<Arc<dyn MailServiceTrait>>,
 }
 
 impl UserService {
-    pub fn new(user_repository: Arc<UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: Arc<dyn UserRepositoryTrait>,
+        audit_service: Arc<AuditService>,
+        mail_service: Option<Arc<dyn MailServiceTrait>>,
+    ) -> Self {
+        Self {
+            user_repository,
+            audit_service,
+            mail_service,
+        }
+    }
+
+    /// Resolves a login identifier: anything with an `@` is an email and a
+    /// NIM is looked up through the organization member linked to the
+    /// account. A NIM shared by several accounts (it is only unique per
+    /// organization) resolves to nobody, as does anything else.
+    async fn find_login_user(&self, identifier: &str) -> Result<Option<User>, UserServiceError> {
+        let identifier = identifier.trim();
+
+        if identifier.contains('@') {
+            return self
+                .user_repository
+                .find_user("email", &normalize_email(identifier))
+                .await
+                .map_err(UserServiceError::from);
+        }
+
+        if !is_nim(identifier) {
+            return Ok(None);
+        }
+
+        let mut users = self
+            .user_repository
+            .find_users_by_member_identifier("nim", identifier)
+            .await?;
+        Ok(if users.len() == 1 { users.pop() } else { None })
     }
 
     pub async fn authenticate_user(
         &self,
-        email: &str,
+        identifier: &str,
         password: &str,
-        messages: &Messages,
-    ) -> Result<(User, String)> {
+    ) -> Result<(User, String), UserServiceError> {
         let user = self
-            .user_repository
-            .find_user("email", email)
-            .await
-            .context(UserServiceError::NotFound.to_message(messages))?
-            .ok_or_else(|| anyhow!(UserServiceError::NotFound.to_message(messages)))?;
+            .find_login_user(identifier)
+            .await?
+            .ok_or(UserServiceError::NotFound)?;
 
-        verify_password(password, &user.password)
-            .map_err(|_| anyhow!(UserServiceError::InvalidCredentials.to_message(messages)))?;
+        if user.locked_until.is_some_and(|until| until > Utc::now()) {
+            return Err(UserServiceError::AccountLocked);
+        }
 
-        let token = generate_jwt(&user.name, &user.email).map_err(|e| {
-            anyhow!(UserServiceError::JwtGenerationError(e.to_string()).to_message(messages))
-        })?;
+        let is_valid = verify_password(password, &user.password)
+            .map_err(|_| UserServiceError::InvalidCredentials)?;
+
+        if !is_valid {
+            if let Err(err) = self
+                .user_repository
+                .record_failed_login(
+                    &user.email,
+                    login_lockout_threshold(),
+                    Utc::now() + login_lockout_duration(),
+                )
+                .await
+            {
+                warn!(
+                    "⚠️ Failed to record failed login for {}: {:?}",
+                    user.email, err
+                );
+            }
+            return Err(UserServiceError::InvalidCredentials);
+        }
+
+        if (user.failed_login_attempts > 0 || user.locked_until.is_some())
+            && let Err(err) = self.user_repository.reset_failed_logins(&user.email).await
+        {
+            warn!(
+                "⚠️ Failed to reset failed logins for {}: {:?}",
+                user.email, err
+            );
+        }
+
+        if password_needs_rehash(&user.password) {
+            self.rehash_password(&user.email, &user.password, password)
+                .await;
+        }
+
+        if user.status != UserStatus::Active {
+            return Err(UserServiceError::AccountInactive);
+        }
+
+        if email_verification_required() && !user.email_verified {
+            return Err(UserServiceError::EmailNotVerified);
+        }
+
+        let token = generate_jwt(&user.name, &user.email, user.token_version)
+            .map_err(|e| UserServiceError::JwtGenerationError(e.to_string()))?;
 
         Ok((user, token))
     }
 
-    pub async fn register_user(
-        &self,
-        new_user: RegisterRequest,
-        messages: &Messages,
-    ) -> Result<User> {
-        let existing_user = self
+    pub async fn is_email_available(&self, email: &str) -> Result<bool, UserServiceError> {
+        let existing = self
             .user_repository
-            .find_user("email", &new_user.email)
-            .await
-            .context(
-                UserServiceError::DbError("Failed to check for duplicate email".into())
-                    .to_message(messages),
-            )?;
+            .find_user("email", &normalize_email(email))
+            .await?;
+
+        Ok(existing.is_none())
+    }
+
+    pub async fn register_user(&self, new_user: RegisterRequest) -> Result<User, UserServiceError> {
+        let email = normalize_email(&new_user.email);
+        let typed_email = new_user.email.trim();
+        let email_display = (typed_email != email).then(|| typed_email.to_string());
+
+        let existing_user = self.user_repository.find_user("email", &email).await?;
 
         if existing_user.is_some() {
-            return Err(anyhow!(
-                UserServiceError::DuplicateEmail.to_message(messages)
-            ));
+            return Err(UserServiceError::DuplicateEmail);
         }
 
-        let hashed_password = hash_password(&new_user.password).map_err(|e| {
-            anyhow!(UserServiceError::PasswordHashingError(e.to_string()).to_message(messages))
-        })?;
+        let hashed_password = hash_password(&new_user.password)
+            .map_err(|e| UserServiceError::PasswordHashingError(e.to_string()))?;
 
         let now = Utc::now();
 
         let user = User {
             _id: Some(ObjectId::new()),
             name: new_user.name,
-            email: new_user.email.clone(),
+            email,
+            email_display,
+            username: None,
             password: hashed_password,
             organization_ids: HashSet::new(),
             owned_organizations: 0,
             subscription_plan: new_user.subscription_plan,
             status: default_status(),
+            email_verified: false,
+            token_version: 0,
+            failed_login_attempts: 0,
+            locked_until: None,
+            version: 0,
             created_at: now,
             updated_at: now,
         };
@@ -128,41 +292,799 @@ impl UserService {
         self.user_repository
             .register_user(&user)
             .await
-            .map_err(|e| anyhow!(UserServiceError::DbError(e.to_string()).to_message(messages)))?;
+            .map_err(|e| {
+                if is_duplicate_key_error(&e) {
+                    UserServiceError::DuplicateEmail
+                } else {
+                    UserServiceError::from(e)
+                }
+            })?;
+
+        self.audit_service.record(AuditLog::new(
+            Some(&user.email),
+            AuditAction::Create,
+            AuditTarget::User,
+            &user.email,
+        ));
+
+        self.send_verification(&user.email).await;
 
         Ok(user)
     }
 
-    pub async fn get_all_users(&self, messages: &Messages) -> Result<Vec<User>> {
+    /// Mails a verification link. A failed delivery does not undo the
+    /// registration.
+    async fn send_verification(&self, email: &str) {
+        let Some(mail_service) = &self.mail_service else {
+            return;
+        };
+
+        let result = match generate_verification_token(email) {
+            Ok(token) => {
+                mail_service
+                    .send(email, MailKind::EmailVerification, &token)
+                    .await
+            }
+            Err(err) => Err(anyhow::anyhow!(err)),
+        };
+        if let Err(err) = result {
+            error!(
+                "❌ Failed to send verification email to {}: {:?}",
+                email, err
+            );
+        }
+    }
+
+    /// Marks the token's email as verified. Returns `None` when the token is
+    /// invalid, expired, or belongs to no account.
+    pub async fn verify_email(&self, token: &str) -> Result<Option<User>, UserServiceError> {
+        let Ok(claims) = verify_verification_token(token) else {
+            return Ok(None);
+        };
+
+        self.user_repository
+            .mark_email_verified(&claims.email)
+            .await
+            .map_err(UserServiceError::from)
+    }
+
+    /// The token version of an active account. `None` for unknown, inactive
+    /// and suspended accounts, whose sessions are no longer honored.
+    pub async fn active_token_version(&self, email: &str) -> Result<Option<u32>, UserServiceError> {
+        Ok(self
+            .user_repository
+            .find_user("email", email)
+            .await?
+            .filter(|user| user.status == UserStatus::Active)
+            .map(|user| user.token_version))
+    }
+
+    pub async fn revoke_sessions(&self, email: &str) -> Result<(), UserServiceError> {
+        self.user_repository
+            .increment_token_version(email)
+            .await?
+            .ok_or(UserServiceError::NotFound)?;
+
+        Ok(())
+    }
+
+    pub async fn get_all_users(
+        &self,
+        filter: Document,
+        pagination: &PaginationQuery,
+        sort: Document,
+    ) -> Result<PaginatedResponse<User>, UserServiceError> {
+        let users = self
+            .user_repository
+            .find_users(
+                filter.clone(),
+                pagination.skip(),
+                pagination.per_page(),
+                sort,
+            )
+            .await?;
+
+        let total = self.user_repository.count_users(filter).await?;
+
+        Ok(PaginatedResponse::new(
+            users,
+            total,
+            pagination.page(),
+            pagination.per_page(),
+        ))
+    }
+
+    pub async fn count_users(&self, filter: Document) -> Result<u64, UserServiceError> {
         self.user_repository
-            .get_all_users()
+            .count_users(filter)
             .await
-            .map_err(|e| anyhow!(UserServiceError::DbError(e.to_string()).to_message(messages)))
+            .map_err(UserServiceError::from)
     }
 
-    pub async fn get_user(&self, email: &str, messages: &Messages) -> Result<Option<User>> {
+    pub async fn get_users_updated_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        pagination: &PaginationQuery,
+    ) -> Result<PaginatedResponse<User>, UserServiceError> {
+        let users = self
+            .user_repository
+            .find_users_updated_before(cutoff, pagination.skip(), pagination.per_page())
+            .await?;
+
+        let total = self
+            .user_repository
+            .count_users_updated_before(cutoff)
+            .await?;
+
+        Ok(PaginatedResponse::new(
+            users,
+            total,
+            pagination.page(),
+            pagination.per_page(),
+        ))
+    }
+
+    /// `Ok(None)` means no account has this email. Any `Err` is a database failure.
+    pub async fn get_user(&self, email: &str) -> Result<Option<User>, UserServiceError> {
         self.user_repository
-            .find_user("email", email)
+            .find_user("email", &normalize_email(email))
             .await
-            .map_err(|e| anyhow!(UserServiceError::DbError(e.to_string()).to_message(messages)))
+            .map_err(UserServiceError::from)
     }
 
     pub async fn update_user(
         &self,
         email: &str,
         user: UpdateUserRequest,
-        messages: &Messages,
-    ) -> Result<UpdateUserRequest> {
+        actor: Option<&str>,
+    ) -> Result<Option<User>, UserServiceError> {
+        let email = &normalize_email(email);
+        let expected_version = user.version;
+        let updated = self.user_repository.update_user(email, user).await?;
+
+        if updated.is_none() && expected_version.is_some() && self.get_user(email).await?.is_some()
+        {
+            return Err(UserServiceError::VersionConflict);
+        }
+
+        if updated.is_some() {
+            self.audit_service.record(AuditLog::new(
+                actor,
+                AuditAction::Update,
+                AuditTarget::User,
+                email,
+            ));
+        }
+
+        Ok(updated)
+    }
+
+    pub async fn update_user_by_id(
+        &self,
+        user_id: &ObjectId,
+        user: UpdateUserRequest,
+        actor: Option<&str>,
+    ) -> Result<Option<User>, UserServiceError> {
+        let expected_version = user.version;
+        let updated = self
+            .user_repository
+            .update_user_by_id(user_id, user)
+            .await?;
+
+        match &updated {
+            Some(user) => self.audit_service.record(AuditLog::new(
+                actor,
+                AuditAction::Update,
+                AuditTarget::User,
+                &user.email,
+            )),
+            None if expected_version.is_some()
+                && self
+                    .user_repository
+                    .find_user_by_id(user_id)
+                    .await?
+                    .is_some() =>
+            {
+                return Err(UserServiceError::VersionConflict);
+            }
+            None => {}
+        }
+
+        Ok(updated)
+    }
+
+    pub async fn update_status(
+        &self,
+        email: &str,
+        status: UserStatus,
+        actor: Option<&str>,
+    ) -> Result<Option<User>, UserServiceError> {
+        let email = &normalize_email(email);
+        let updated = self.user_repository.update_status(email, &status).await?;
+
+        if updated.is_some() {
+            self.audit_service.record(AuditLog::new(
+                actor,
+                AuditAction::Update,
+                AuditTarget::User,
+                email,
+            ));
+        }
+
+        Ok(updated)
+    }
+
+    /// Unknown accounts fail like a wrong password, so the endpoint cannot be
+    /// used to probe which emails are registered.
+    pub async fn update_password(
+        &self,
+        email: &str,
+        current_password: &str,
+        new_password: &str,
+        actor: Option<&str>,
+    ) -> Result<(), UserServiceError> {
+        let email = &normalize_email(email);
+        let user = self
+            .user_repository
+            .find_user("email", email)
+            .await?
+            .ok_or(UserServiceError::InvalidCredentials)?;
+
+        let is_valid = verify_password(current_password, &user.password)
+            .map_err(|_| UserServiceError::InvalidCredentials)?;
+
+        if !is_valid {
+            return Err(UserServiceError::InvalidCredentials);
+        }
+
+        let hashed_password = hash_password(new_password)
+            .map_err(|e| UserServiceError::PasswordHashingError(e.to_string()))?;
+
         self.user_repository
-            .update_user(email, user)
+            .update_password(email, &hashed_password)
+            .await?;
+
+        self.audit_service.record(AuditLog::new(
+            actor,
+            AuditAction::Update,
+            AuditTarget::User,
+            email,
+        ));
+
+        Ok(())
+    }
+
+    async fn rehash_password(&self, email: &str, current_hash: &str, password: &str) {
+        let new_hash = match hash_password(password) {
+            Ok(hash) => hash,
+            Err(err) => {
+                warn!("⚠️ Failed to rehash password for {}: {}", email, err);
+                return;
+            }
+        };
+
+        match self
+            .user_repository
+            .replace_password_hash(email, current_hash, &new_hash)
             .await
-            .map_err(|e| anyhow!(UserServiceError::DbError(e.to_string()).to_message(messages)))
+        {
+            Ok(true) => info!("🔐 Upgraded password hash for {}", email),
+            Ok(false) => {}
+            Err(err) => warn!(
+                "⚠️ Failed to store rehashed password for {}: {:?}",
+                email, err
+            ),
+        }
     }
 
-    pub async fn delete_user(&self, email: &str, messages: &Messages) -> Result<()> {
-        self.user_repository
+    pub async fn delete_user(
+        &self,
+        email: &str,
+        actor: Option<&str>,
+    ) -> Result<(), UserServiceError> {
+        let email = &normalize_email(email);
+        let deleted = self
+            .user_repository
             .delete_user(email)
             .await
-            .map_err(|e| anyhow!(UserServiceError::DbError(e.to_string()).to_message(messages)))
+            .map_err(|e| UserServiceError::DbError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(UserServiceError::NotFound);
+        }
+
+        self.audit_service.record(AuditLog::new(
+            actor,
+            AuditAction::Delete,
+            AuditTarget::User,
+            email,
+        ));
+
+        Ok(())
+    }
+
+    pub async fn delete_user_by_id(
+        &self,
+        user_id: &ObjectId,
+        actor: Option<&str>,
+    ) -> Result<(), UserServiceError> {
+        let deleted = self
+            .user_repository
+            .delete_user_by_id(user_id)
+            .await?
+            .ok_or(UserServiceError::NotFound)?;
+
+        self.audit_service.record(AuditLog::new(
+            actor,
+            AuditAction::Delete,
+            AuditTarget::User,
+            &deleted.email,
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::in_memory_user_repository::{
+        InMemoryUserRepository, detached_audit_service, duplicate_key_error, init_test_env, user,
+        user_service,
+    };
+    use crate::services::mail_service::RecordingMailService;
+    use crate::types::models::user::subscription::SubscriptionPlan;
+    use crate::utils::{
+        auth_utils::{hash_password_with_params, verify_jwt},
+        lang::Lang,
+    };
+    use actix_web::{ResponseError, http::StatusCode};
+
+    fn register_request(email: &str) -> RegisterRequest {
+        RegisterRequest {
+            name: "New User".to_string(),
+            email: email.to_string(),
+            password: "Str0ng!Passw0rd".to_string(),
+            subscription_plan: SubscriptionPlan::Free,
+        }
+    }
+
+    fn status_of(err: &UserServiceError) -> StatusCode {
+        err.to_api_error(&Messages::new(Lang::En)).status_code()
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_issues_a_token_for_valid_credentials() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository).await;
+
+        let (user, token) = service
+            .authenticate_user("ana@example.com", "correct horse")
+            .await
+            .expect("valid credentials");
+
+        assert_eq!(user.email, "ana@example.com");
+        assert_eq!(verify_jwt(&token).expect("a valid token").email, user.email);
+    }
+
+    #[actix_rt::test]
+    async fn the_same_account_logs_in_by_email_or_nim() {
+        let ana = user("ana@example.com", "correct horse");
+        let ana_id = ana._id.unwrap();
+        let repository = Arc::new(
+            InMemoryUserRepository::with_users(vec![ana, user("bob@example.com", "hunter22")])
+                .with_member_identifier("nim", "2021000001", ana_id),
+        );
+        let service = user_service(repository).await;
+
+        let (by_email, _) = service
+            .authenticate_user("ana@example.com", "correct horse")
+            .await
+            .expect("email login succeeds");
+        let (by_nim, _) = service
+            .authenticate_user(" 2021000001 ", "correct horse")
+            .await
+            .expect("NIM login succeeds");
+
+        assert_eq!(by_email._id, Some(ana_id));
+        assert_eq!(by_nim._id, Some(ana_id));
+        assert!(matches!(
+            service
+                .authenticate_user("2021000002", "correct horse")
+                .await,
+            Err(UserServiceError::NotFound)
+        ));
+        assert!(matches!(
+            service.authenticate_user("ana", "correct horse").await,
+            Err(UserServiceError::NotFound)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_rejects_a_wrong_password_and_counts_it() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository.clone()).await;
+
+        let err = service
+            .authenticate_user("ana@example.com", "battery staple")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, UserServiceError::InvalidCredentials));
+        assert_eq!(
+            repository
+                .user("ana@example.com")
+                .unwrap()
+                .failed_login_attempts,
+            1
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_password_rejects_unknown_accounts_like_a_wrong_password() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository).await;
+
+        let unknown = service
+            .update_password("bob@example.com", "correct horse", "Str0ng!Passw0rd", None)
+            .await
+            .unwrap_err();
+        let wrong = service
+            .update_password("ana@example.com", "battery staple", "Str0ng!Passw0rd", None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(unknown, UserServiceError::InvalidCredentials));
+        assert!(matches!(wrong, UserServiceError::InvalidCredentials));
+        assert_eq!(status_of(&unknown), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_rt::test]
+    async fn dormant_users_are_paginated_oldest_first() {
+        let now = Utc::now();
+        let aged = |email: &str, days: i64| {
+            let mut account = user(email, "correct horse");
+            account.updated_at = now - chrono::Duration::days(days);
+            account
+        };
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![
+            aged("recent@example.com", 1),
+            aged("older@example.com", 60),
+            aged("oldest@example.com", 90),
+        ]));
+        let service = user_service(repository).await;
+        let cutoff = now - chrono::Duration::days(30);
+
+        let page = service
+            .get_users_updated_before(
+                cutoff,
+                &PaginationQuery {
+                    page: 2,
+                    per_page: 1,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 2);
+        assert_eq!(page.page, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].email, "older@example.com");
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_reports_unknown_accounts_as_not_found() {
+        let service = user_service(Arc::new(InMemoryUserRepository::default())).await;
+
+        let err = service
+            .authenticate_user("nobody@example.com", "whatever")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, UserServiceError::NotFound));
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_surfaces_database_failures() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        repository.fail_next(1);
+        let service = user_service(repository).await;
+
+        let err = service
+            .authenticate_user("ana@example.com", "whatever")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, UserServiceError::DbError(_)));
+    }
+
+    #[actix_rt::test]
+    async fn register_user_stores_a_hashed_password() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let service = user_service(repository.clone()).await;
+
+        let registered = service
+            .register_user(register_request("new@example.com"))
+            .await
+            .expect("registration succeeds");
+
+        let stored = repository.user("new@example.com").expect("a stored user");
+        assert_eq!(stored._id, registered._id);
+        assert_ne!(stored.password, "Str0ng!Passw0rd");
+        assert!(verify_password("Str0ng!Passw0rd", &stored.password).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn register_user_rejects_a_taken_email() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "taken@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository).await;
+
+        let err = service
+            .register_user(register_request("taken@example.com"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, UserServiceError::DuplicateEmail));
+    }
+
+    #[actix_rt::test]
+    async fn register_user_folds_the_email_and_keeps_the_typed_casing() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let service = user_service(repository.clone()).await;
+
+        let registered = service
+            .register_user(register_request(" Mixed.Case@Example.COM "))
+            .await
+            .expect("registration succeeds");
+
+        assert_eq!(registered.email, "mixed.case@example.com");
+        assert_eq!(
+            registered.email_display.as_deref(),
+            Some("Mixed.Case@Example.COM")
+        );
+        assert!(repository.user("mixed.case@example.com").is_some());
+        assert!(
+            !service
+                .is_email_available("mixed.case@example.com")
+                .await
+                .unwrap()
+        );
+
+        let err = service
+            .register_user(register_request("MIXED.case@example.com"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, UserServiceError::DuplicateEmail));
+    }
+
+    #[actix_rt::test]
+    async fn login_ignores_the_casing_used_at_registration() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let service = user_service(repository).await;
+
+        service
+            .register_user(register_request("User@Example.com"))
+            .await
+            .expect("registration succeeds");
+
+        let (user, _) = service
+            .authenticate_user("user@example.com", "Str0ng!Passw0rd")
+            .await
+            .expect("lowercase login succeeds");
+
+        assert_eq!(user.email, "user@example.com");
+        assert_eq!(user.email_display.as_deref(), Some("User@Example.com"));
+    }
+
+    #[actix_rt::test]
+    async fn email_path_lookups_ignore_the_case_of_the_address() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository.clone()).await;
+
+        assert!(service.get_user("Ana@EXAMPLE.com").await.unwrap().is_some());
+        service
+            .update_status(" ana@Example.com", UserStatus::Suspended, None)
+            .await
+            .unwrap()
+            .expect("the user exists");
+        service
+            .delete_user("ana@example.COM", None)
+            .await
+            .expect("the user is deleted");
+
+        assert!(repository.user("ana@example.com").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn registration_mails_a_verification_token() {
+        init_test_env();
+        let mail = Arc::new(RecordingMailService::default());
+        let service = UserService::new(
+            Arc::new(InMemoryUserRepository::default()),
+            detached_audit_service().await,
+            Some(mail.clone()),
+        );
+
+        service
+            .register_user(register_request("new@example.com"))
+            .await
+            .expect("registration succeeds");
+
+        let sent = mail.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let (email, kind, token) = &sent[0];
+        assert_eq!(email, "new@example.com");
+        assert_eq!(*kind, MailKind::EmailVerification);
+        assert_eq!(
+            verify_verification_token(token).unwrap().email,
+            "new@example.com"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn delete_user_by_id_removes_the_account() {
+        let existing = user("ana@example.com", "correct horse");
+        let user_id = existing._id.unwrap();
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![existing]));
+        let service = user_service(repository.clone()).await;
+
+        service
+            .delete_user_by_id(&user_id, Some("admin@example.com"))
+            .await
+            .expect("the user is deleted");
+
+        assert!(repository.user("ana@example.com").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn delete_user_by_id_reports_a_missing_user_as_not_found() {
+        let service = user_service(Arc::new(InMemoryUserRepository::default())).await;
+
+        let err = service
+            .delete_user_by_id(&ObjectId::new(), None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, UserServiceError::NotFound));
+    }
+
+    #[test]
+    fn duplicate_key_errors_map_to_duplicate_email() {
+        assert!(is_duplicate_key_error(&duplicate_key_error()));
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_upgrades_a_low_cost_hash() {
+        let mut weak = user("ana@example.com", "correct horse");
+        let low_cost = argon2::Params::new(8, 1, 1, None).unwrap();
+        weak.password = hash_password_with_params("correct horse", low_cost).unwrap();
+        let old_hash = weak.password.clone();
+        assert!(password_needs_rehash(&old_hash));
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![weak]));
+        let service = user_service(repository.clone()).await;
+
+        service
+            .authenticate_user("ana@example.com", "correct horse")
+            .await
+            .unwrap();
+
+        let stored = repository.user("ana@example.com").unwrap().password;
+        assert_ne!(stored, old_hash);
+        assert!(!password_needs_rehash(&stored));
+        assert!(verify_password("correct horse", &stored).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn update_status_persists_the_new_status() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository.clone()).await;
+
+        let updated = service
+            .update_status("ana@example.com", UserStatus::Suspended, None)
+            .await
+            .unwrap()
+            .expect("the user exists");
+
+        assert_eq!(updated.status, UserStatus::Suspended);
+        assert_eq!(
+            repository.user("ana@example.com").unwrap().status,
+            UserStatus::Suspended
+        );
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_user_rejects_accounts_that_are_not_active() {
+        for status in [UserStatus::Inactive, UserStatus::Suspended] {
+            let mut suspended = user("ana@example.com", "correct horse");
+            suspended.status = status;
+            let repository = Arc::new(InMemoryUserRepository::with_users(vec![suspended]));
+            let service = user_service(repository).await;
+
+            let err = service
+                .authenticate_user("ana@example.com", "correct horse")
+                .await
+                .unwrap_err();
+
+            assert!(matches!(err, UserServiceError::AccountInactive));
+        }
+    }
+
+    #[actix_rt::test]
+    async fn sessions_of_deactivated_accounts_are_not_honored() {
+        let repository = Arc::new(InMemoryUserRepository::with_users(vec![user(
+            "ana@example.com",
+            "correct horse",
+        )]));
+        let service = user_service(repository).await;
+        assert_eq!(
+            service
+                .active_token_version("ana@example.com")
+                .await
+                .unwrap(),
+            Some(0)
+        );
+
+        service
+            .update_status("ana@example.com", UserStatus::Inactive, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service
+                .active_token_version("ana@example.com")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn errors_map_to_their_status_codes() {
+        assert_eq!(
+            status_of(&UserServiceError::NotFound),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status_of(&UserServiceError::InvalidCredentials),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_of(&UserServiceError::AccountLocked),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_of(&UserServiceError::AccountInactive),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status_of(&UserServiceError::DuplicateEmail),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status_of(&UserServiceError::VersionConflict),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status_of(&UserServiceError::DbError("down".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
     }
 }
@@ -1,4 +1,8 @@
-use crate::config::database::{connect_to_database, create_unique_indexes};
+use crate::config::database::{
+    connect_to_database, count_organization_members, create_attendance_indexes,
+    create_idempotency_indexes, create_member_indexes, create_password_reset_indexes,
+    create_search_indexes, create_unique_indexes, normalize_user_emails,
+};
 use std::sync::Arc;
 
 pub async fn setup_database() -> Arc<mongodb::Client> {
@@ -12,5 +16,46 @@ pub async fn setup_database() -> Arc<mongodb::Client> {
         .expect("❌ Failed to create indexes");
     println!("✅ Unique indexes created successfully");
 
+    match normalize_user_emails(&client)
+        .await
+        .expect("❌ Failed to normalize user emails")
+    {
+        Some(normalized) => println!("✅ Normalized {} user emails", normalized),
+        None => println!("✅ User emails already normalized"),
+    }
+
+    match count_organization_members(&client)
+        .await
+        .expect("❌ Failed to count organization members")
+    {
+        Some(counted) => println!("✅ Counted members of {} organizations", counted),
+        None => println!("✅ Organization member counts already set"),
+    }
+
+    create_search_indexes(&client)
+        .await
+        .expect("❌ Failed to create search indexes");
+    println!("✅ Search indexes created successfully");
+
+    create_member_indexes(&client)
+        .await
+        .expect("❌ Failed to create member indexes");
+    println!("✅ Member indexes created successfully");
+
+    create_attendance_indexes(&client)
+        .await
+        .expect("❌ Failed to create attendance indexes");
+    println!("✅ Attendance indexes created successfully");
+
+    create_idempotency_indexes(&client)
+        .await
+        .expect("❌ Failed to create idempotency indexes");
+    println!("✅ Idempotency indexes created successfully");
+
+    create_password_reset_indexes(&client)
+        .await
+        .expect("❌ Failed to create password reset indexes");
+    println!("✅ Password reset indexes created successfully");
+
     Arc::new(client)
 }
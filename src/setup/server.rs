@@ -0,0 +1,49 @@
+use actix_web::{App, HttpServer, web};
+use log::info;
+use std::{io, net::SocketAddr, sync::Arc, thread};
+
+use crate::{
+    config::cors::{CorsSettings, configure_cors},
+    utils::{auth_utils::load_password_hash_params, lang::Lang},
+};
+
+const MAX_WORKERS: usize = 4;
+const SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
+pub async fn run_server<F>(
+    config: F,
+    addr: SocketAddr,
+    client: Arc<mongodb::Client>,
+) -> io::Result<()>
+where
+    F: FnOnce(&mut web::ServiceConfig) + Send + Clone + 'static,
+{
+    let workers = thread::available_parallelism()
+        .map(|count| count.get().min(MAX_WORKERS))
+        .unwrap_or(1);
+
+    let cors =
+        CorsSettings::from_env().map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    load_password_hash_params().map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    Lang::load_deployment_default()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .wrap(configure_cors(&cors))
+            .configure(config.clone())
+    })
+    .workers(workers)
+    .shutdown_timeout(SHUTDOWN_TIMEOUT_SECS)
+    .bind(addr)?
+    .run();
+
+    info!("🌐 Listening on {addr}");
+    let result = server.await;
+    info!("🛑 HTTP server stopped, in-flight requests drained");
+
+    Arc::unwrap_or_clone(client).shutdown().await;
+    info!("🔌 MongoDB client shut down");
+
+    result
+}
@@ -1,24 +1,114 @@
 use crate::{
     repositories::{
-        organization_repository::OrganizationRepository, user_repository::UserRepository,
+        attendance_repository::AttendanceRepository, audit_log_repository::AuditLogRepository,
+        class_session_repository::ClassSessionRepository,
+        excuse_request_repository::ExcuseRequestRepository,
+        idempotency_repository::IdempotencyRepository,
+        organization_member_repository::OrganizationMemberRepository,
+        organization_repository::OrganizationRepository,
+        password_reset_repository::PasswordResetRepository, user_repository::UserRepository,
+    },
+    services::{
+        attendance_service::AttendanceService, audit_service::AuditService,
+        excuse_service::ExcuseService, mail_service::mail_service_from_env,
+        organization_member_service::OrganizationMemberService,
+        organization_service::OrganizationService, password_reset_service::PasswordResetService,
+        user_service::UserService,
     },
-    services::{organization_service::OrganizationService, user_service::UserService},
 };
 use std::sync::Arc;
 
 pub async fn setup_services(
     client: &Arc<mongodb::Client>,
-) -> (Arc<UserService>, Arc<OrganizationService>) {
+) -> (
+    Arc<UserService>,
+    Arc<OrganizationService>,
+    Arc<AttendanceService>,
+    Arc<OrganizationMemberService>,
+    Arc<PasswordResetService>,
+    Arc<ExcuseService>,
+) {
     let user_repository = UserRepository::new(client)
         .await
         .expect("❌ Failed to initialize UserRepository");
     let organization_repository = OrganizationRepository::new(client)
         .await
         .expect("❌ Failed to initialize OrganizationRepository");
+    let idempotency_repository = IdempotencyRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize IdempotencyRepository");
+    let attendance_repository = AttendanceRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize AttendanceRepository");
+    let organization_member_repository = OrganizationMemberRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize OrganizationMemberRepository");
+    let audit_log_repository = AuditLogRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize AuditLogRepository");
+    let password_reset_repository = PasswordResetRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize PasswordResetRepository");
+    let class_session_repository = ClassSessionRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize ClassSessionRepository");
+    let excuse_request_repository = ExcuseRequestRepository::new(client)
+        .await
+        .expect("❌ Failed to initialize ExcuseRequestRepository");
+
+    let user_repository = Arc::new(user_repository);
+    let organization_repository = Arc::new(organization_repository);
+    let organization_member_repository = Arc::new(organization_member_repository);
+
+    let audit_service = Arc::new(AuditService::new(Arc::new(audit_log_repository)));
+    let mail_service = mail_service_from_env().expect("❌ Failed to configure mail delivery");
+
+    let user_service = Arc::new(UserService::new(
+        user_repository.clone(),
+        audit_service.clone(),
+        mail_service.clone(),
+    ));
+    let organization_service = Arc::new(OrganizationService::new(
+        organization_repository.clone(),
+        organization_member_repository.clone(),
+        user_repository.clone(),
+        Arc::new(idempotency_repository),
+        audit_service,
+    ));
+
+    let attendance_service = Arc::new(AttendanceService::new(
+        Arc::new(attendance_repository),
+        organization_repository.clone(),
+        organization_member_repository.clone(),
+        Arc::new(class_session_repository),
+        user_repository.clone(),
+    ));
+
+    let excuse_service = Arc::new(ExcuseService::new(
+        Arc::new(excuse_request_repository),
+        organization_repository.clone(),
+        organization_member_repository.clone(),
+        user_repository.clone(),
+    ));
+
+    let organization_member_service = Arc::new(OrganizationMemberService::new(
+        organization_member_repository,
+        organization_repository,
+        user_repository.clone(),
+    ));
 
-    let user_service = Arc::new(UserService::new(Arc::new(user_repository)));
-    let organization_service =
-        Arc::new(OrganizationService::new(Arc::new(organization_repository)));
+    let password_reset_service = Arc::new(PasswordResetService::new(
+        Arc::new(password_reset_repository),
+        user_repository,
+        mail_service,
+    ));
 
-    (user_service, organization_service)
+    (
+        user_service,
+        organization_service,
+        attendance_service,
+        organization_member_service,
+        password_reset_service,
+        excuse_service,
+    )
 }
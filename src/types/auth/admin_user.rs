@@ -0,0 +1,49 @@
+use actix_web::{FromRequest, HttpRequest, dev::Payload};
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    constants::ADMIN_EMAILS,
+    types::{auth::authenticated_user::AuthenticatedUser, responses::api_error::ApiError},
+    utils::{
+        auth_utils::normalize_email,
+        locale_utils::{Messages, get_lang},
+    },
+};
+
+/// An authenticated user whose email is listed in `ADMIN_EMAILS`. Operators
+/// are configured per deployment rather than stored on the account.
+#[derive(Debug)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl FromRequest for AdminUser {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let authenticated = AuthenticatedUser::from_request(req, payload);
+        let lang = get_lang(req);
+
+        Box::pin(async move {
+            let user = authenticated.await?;
+
+            if !is_admin_email(&user.claims.email) {
+                let messages = Messages::new(lang);
+                return Err(ApiError::Forbidden(messages.get_auth_message(
+                    "session.admin_required",
+                    "This action requires an administrator.",
+                ))
+                .into());
+            }
+
+            Ok(Self(user))
+        })
+    }
+}
+
+pub fn is_admin_email(email: &str) -> bool {
+    let email = normalize_email(email);
+    ADMIN_EMAILS
+        .split(',')
+        .map(normalize_email)
+        .any(|admin| !admin.is_empty() && admin == email)
+}
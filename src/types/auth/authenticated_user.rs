@@ -0,0 +1,71 @@
+use actix_web::{FromRequest, HttpRequest, dev::Payload, web};
+use futures::future::LocalBoxFuture;
+use log::error;
+use std::sync::Arc;
+
+use crate::{
+    constants::COOKIE_NAME,
+    services::user_service::UserService,
+    types::{
+        auth::{claims::Claims, jwt_error::JwtError},
+        responses::api_error::ApiError,
+    },
+    utils::{
+        auth_utils::verify_jwt,
+        locale_utils::{Lang, Messages, get_lang},
+    },
+};
+
+#[derive(Debug)]
+pub struct AuthenticatedUser {
+    pub claims: Claims,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let claims = req
+            .cookie(COOKIE_NAME.as_str())
+            .ok_or(JwtError::Invalid)
+            .and_then(|cookie| verify_jwt(cookie.value()));
+        let user_service = req.app_data::<web::Data<Arc<UserService>>>().cloned();
+        let lang = get_lang(req);
+
+        Box::pin(async move {
+            let claims = claims.map_err(|err| session_error(err, lang))?;
+
+            // Tokens minted before the user's last revoke-all carry a stale
+            // version, and deactivated accounts have no valid version at all.
+            if let Some(user_service) = user_service {
+                let current = user_service
+                    .active_token_version(&claims.email)
+                    .await
+                    .map_err(|err| {
+                        error!("❌ Failed to load token version: {:?}", err);
+                        session_error(JwtError::Invalid, lang)
+                    })?;
+                if current != Some(claims.ver) {
+                    return Err(session_error(JwtError::Invalid, lang));
+                }
+            }
+
+            Ok(Self { claims })
+        })
+    }
+}
+
+fn session_error(err: JwtError, lang: Lang) -> actix_web::Error {
+    let messages = Messages::new(lang);
+    match err {
+        JwtError::Expired => ApiError::TokenExpired(messages.get_auth_message(
+            "session.token_expired",
+            "Your session has expired. Please log in again.",
+        )),
+        JwtError::Invalid => ApiError::Unauthorized(
+            messages.get_auth_message("session.unauthorized", "Authentication is required."),
+        ),
+    }
+    .into()
+}
@@ -5,4 +5,9 @@ pub struct Claims {
     pub name: String,
     pub email: String,
     pub exp: usize,
+    pub iat: usize,
+    pub iss: String,
+    pub aud: String,
+    #[serde(default)]
+    pub ver: u32,
 }
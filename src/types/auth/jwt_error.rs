@@ -0,0 +1,18 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum JwtError {
+    Expired,
+    Invalid,
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtError::Expired => write!(f, "Token has expired"),
+            JwtError::Invalid => write!(f, "Token is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for JwtError {}
@@ -1 +1,5 @@
+pub mod admin_user;
+pub mod authenticated_user;
 pub mod claims;
+pub mod jwt_error;
+pub mod verification_claims;
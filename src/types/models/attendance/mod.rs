@@ -1,4 +1,5 @@
 pub mod attendance_method;
 pub mod attendance_status;
 pub mod attendance_type;
+pub mod excuse_status;
 pub mod geolocation;
@@ -0,0 +1,3 @@
+pub fn default_timezone() -> String {
+    "UTC".to_string()
+}
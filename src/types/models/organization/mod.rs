@@ -1 +1,2 @@
+pub mod defaults;
 pub mod organization_limit;
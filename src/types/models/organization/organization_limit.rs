@@ -6,3 +6,10 @@ pub struct OrganizationLimits {
     pub max_users: u32,
     pub max_attendance_logs: u32,
 }
+
+impl OrganizationLimits {
+    pub fn fits_within(&self, ceiling: &OrganizationLimits) -> bool {
+        self.max_users <= ceiling.max_users
+            && self.max_attendance_logs <= ceiling.max_attendance_logs
+    }
+}
@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::Display;
+use strum_macros::{Display, EnumString};
 
-#[derive(Debug, Clone, PartialEq, Display, Serialize, Deserialize)]
-#[strum(serialize_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Display, Serialize, Deserialize, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Role {
     Admin,
     Staff,
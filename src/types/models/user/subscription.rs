@@ -25,6 +25,10 @@ pub enum SubscriptionPlan {
 }
 
 impl SubscriptionPlan {
+    pub fn is_self_service(&self) -> bool {
+        matches!(self, SubscriptionPlan::Free)
+    }
+
     pub fn max_organizations(&self) -> u32 {
         match self {
             SubscriptionPlan::Free => 1,
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct AttendanceSummaryQuery {
+    pub from: Option<DateTime<Utc>>,
+
+    pub to: Option<DateTime<Utc>>,
+}
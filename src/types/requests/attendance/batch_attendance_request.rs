@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::types::models::attendance::{
+    attendance_method::AttendanceMethod, attendance_status::AttendanceStatus,
+    geolocation::GeoLocation,
+};
+
+fn default_status() -> AttendanceStatus {
+    AttendanceStatus::Present
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AttendanceCheckIn {
+    pub member_id: String,
+
+    #[serde(default = "default_status")]
+    pub status: AttendanceStatus,
+
+    pub method: AttendanceMethod,
+
+    #[serde(default)]
+    pub clock_in: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub location: Option<GeoLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchAttendanceRequest {
+    pub organization_id: String,
+
+    /// Check-ins after the class start plus the grace period are marked late.
+    #[serde(default)]
+    pub class_id: Option<String>,
+
+    pub entries: Vec<AttendanceCheckIn>,
+}
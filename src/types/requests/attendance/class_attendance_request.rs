@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::types::models::attendance::attendance_status::AttendanceStatus;
+
+#[derive(Debug, Deserialize)]
+pub struct ClassAttendanceMark {
+    pub member_id: String,
+
+    pub status: AttendanceStatus,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClassAttendanceRequest {
+    pub members: Vec<ClassAttendanceMark>,
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateClassRequest {
+    pub organization_id: String,
+
+    pub name: String,
+
+    pub starts_at: DateTime<Utc>,
+
+    /// A user holding the Admin or Staff role in the organization, who may
+    /// mark the class roster.
+    #[serde(default)]
+    pub instructor_id: Option<String>,
+
+    #[serde(default)]
+    pub check_in_opens_minutes_before: Option<u32>,
+
+    #[serde(default)]
+    pub check_in_closes_minutes_after: Option<u32>,
+}
@@ -0,0 +1,6 @@
+pub mod attendance_summary_query;
+pub mod batch_attendance_request;
+pub mod class_attendance_request;
+pub mod create_class_request;
+pub mod submit_excuse_request;
+pub mod today_attendance_query;
@@ -0,0 +1,11 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitExcuseRequest {
+    pub organization_id: String,
+
+    pub date: NaiveDate,
+
+    pub reason: String,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TodayAttendanceQuery {
+    /// Limits the statuses to check-ins for this class.
+    pub class_id: Option<String>,
+}
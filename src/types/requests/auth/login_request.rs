@@ -2,6 +2,9 @@ use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
-    pub email: String,
+    /// An email address or a student id (NIM). Clients that still
+    /// send `email` keep working.
+    #[serde(alias = "email")]
+    pub identifier: String,
     pub password: String,
 }
@@ -1,2 +1,6 @@
+pub mod email_availability_query;
 pub mod login_request;
+pub mod password_reset_request;
 pub mod register_request;
+pub mod reset_password_request;
+pub mod verify_email_query;
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+const DEFAULT_PAGE: u64 = 1;
+const DEFAULT_PER_PAGE: u64 = 20;
+const MAX_PER_PAGE: u64 = 100;
+
+fn default_page() -> u64 {
+    DEFAULT_PAGE
+}
+
+fn default_per_page() -> u64 {
+    DEFAULT_PER_PAGE
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct PaginationQuery {
+    #[serde(default = "default_page")]
+    pub page: u64,
+
+    #[serde(default = "default_per_page")]
+    pub per_page: u64,
+}
+
+impl Default for PaginationQuery {
+    fn default() -> Self {
+        Self {
+            page: DEFAULT_PAGE,
+            per_page: DEFAULT_PER_PAGE,
+        }
+    }
+}
+
+impl PaginationQuery {
+    pub fn page(&self) -> u64 {
+        self.page.max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page.clamp(1, MAX_PER_PAGE)
+    }
+
+    /// Capped at `i64::MAX`, the largest skip MongoDB accepts, so an absurd
+    /// `page` yields an empty page instead of overflowing.
+    pub fn skip(&self) -> u64 {
+        (self.page() - 1)
+            .saturating_mul(self.per_page())
+            .min(i64::MAX as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_counts_the_items_on_previous_pages() {
+        let query = PaginationQuery {
+            page: 3,
+            per_page: 20,
+        };
+
+        assert_eq!(query.skip(), 40);
+    }
+
+    #[test]
+    fn skip_does_not_overflow_on_a_huge_page() {
+        let query = PaginationQuery {
+            page: u64::MAX,
+            per_page: MAX_PER_PAGE,
+        };
+
+        assert_eq!(query.skip(), i64::MAX as u64);
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let query = PaginationQuery {
+            page: 0,
+            per_page: 10_000,
+        };
+
+        assert_eq!(query.page(), 1);
+        assert_eq!(query.per_page(), MAX_PER_PAGE);
+        assert_eq!(query.skip(), 0);
+    }
+}
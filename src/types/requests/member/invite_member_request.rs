@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::types::models::user::role::Role;
+
+fn default_role() -> Role {
+    Role::User
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InviteMemberRequest {
+    pub name: String,
+
+    #[serde(default = "default_role")]
+    pub role: Role,
+
+    #[serde(default)]
+    pub identifiers: HashMap<String, String>,
+}
@@ -0,0 +1,2 @@
+pub mod invite_member_request;
+pub mod update_member_role_request;
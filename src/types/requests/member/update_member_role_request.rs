@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+use crate::types::models::user::role::Role;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemberRoleRequest {
+    pub role: Role,
+}
@@ -1,2 +1,6 @@
+pub mod attendance;
 pub mod auth;
+pub mod common;
+pub mod member;
+pub mod organization;
 pub mod user;
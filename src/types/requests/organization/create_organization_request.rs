@@ -0,0 +1,80 @@
+use bson::oid::ObjectId;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::{
+    models::organization_model::Organization,
+    types::models::organization::{
+        defaults::default_timezone, organization_limit::OrganizationLimits,
+    },
+    utils::lang::Lang,
+};
+
+/// The body of a new organization. The owner is always the caller, so an
+/// `owner_id` in the body is ignored.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+
+    pub email: String,
+
+    pub password: String,
+
+    #[serde(default)]
+    pub logo_url: Option<String>,
+
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    #[serde(default = "Lang::deployment_default")]
+    pub default_lang: Lang,
+
+    pub limits: OrganizationLimits,
+}
+
+impl CreateOrganizationRequest {
+    pub fn into_organization(self, owner_id: ObjectId) -> Organization {
+        let now = Utc::now();
+
+        Organization {
+            _id: None,
+            name: self.name,
+            email: self.email,
+            owner_id,
+            password: self.password,
+            logo_url: self.logo_url,
+            timezone: self.timezone,
+            default_lang: self.default_lang,
+            version: None,
+            created_at: now,
+            updated_at: now,
+            limits: self.limits,
+            member_count: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn the_owner_is_the_caller_not_the_body() {
+        let caller = ObjectId::new();
+        let request: CreateOrganizationRequest = serde_json::from_value(json!({
+            "name": "Organization",
+            "email": "org@example.com",
+            "owner_id": { "$oid": "665f1f77bcf86cd799439011" },
+            "password": "Securepassword123.",
+            "limits": { "max_users": 10, "max_attendance_logs": 5000 },
+        }))
+        .unwrap();
+
+        let organization = request.into_organization(caller);
+
+        assert_eq!(organization.owner_id, caller);
+        assert_eq!(organization.timezone, "UTC");
+        assert!(organization._id.is_none());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod create_organization_request;
+pub mod organization_search_query;
+pub mod transfer_ownership_request;
+pub mod update_organization_request;
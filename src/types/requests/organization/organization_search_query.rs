@@ -0,0 +1,43 @@
+use bson::{Document, doc, oid::ObjectId};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct OrganizationSearchQuery {
+    pub q: Option<String>,
+
+    pub owner_id: Option<String>,
+}
+
+impl OrganizationSearchQuery {
+    pub fn term(&self) -> Option<&str> {
+        self.q
+            .as_deref()
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+    }
+
+    /// Matches the name case-insensitively and narrows to one owner. An
+    /// invalid `owner_id` is returned as `(param, value)`.
+    pub fn filter_document(&self) -> Result<Document, (&'static str, String)> {
+        let mut filter = Document::new();
+
+        if let Some(term) = self.term() {
+            filter.insert(
+                "name",
+                doc! { "$regex": regex::escape(term), "$options": "i" },
+            );
+        }
+        if let Some(owner_id) = self
+            .owner_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|owner_id| !owner_id.is_empty())
+        {
+            let owner_id =
+                ObjectId::parse_str(owner_id).map_err(|_| ("owner_id", owner_id.to_string()))?;
+            filter.insert("owner_id", owner_id);
+        }
+
+        Ok(filter)
+    }
+}
@@ -0,0 +1,7 @@
+use bson::oid::ObjectId;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TransferOwnershipRequest {
+    pub new_owner_id: ObjectId,
+}
@@ -0,0 +1,50 @@
+use crate::{
+    types::models::organization::organization_limit::OrganizationLimits, utils::lang::Lang,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Ownership moves through the transfer endpoint, and the rest is maintained
+/// by the server.
+pub const IMMUTABLE_ORGANIZATION_FIELDS: [&str; 5] =
+    ["_id", "owner_id", "password", "created_at", "updated_at"];
+
+/// The fields an owner or admin may change. Omitted fields keep their value.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct UpdateOrganizationRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub email: Option<String>,
+
+    #[serde(default)]
+    pub logo_url: Option<String>,
+
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    #[serde(default)]
+    pub default_lang: Option<Lang>,
+
+    #[serde(default)]
+    pub limits: Option<OrganizationLimits>,
+
+    /// The `version` last read by the client. When given, the update only
+    /// applies if nobody changed the organization in the meantime.
+    #[serde(default)]
+    pub version: Option<u32>,
+
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl UpdateOrganizationRequest {
+    pub fn immutable_field(&self) -> Option<&'static str> {
+        IMMUTABLE_ORGANIZATION_FIELDS
+            .iter()
+            .find(|field| self.extra.contains_key(**field))
+            .copied()
+    }
+}
@@ -0,0 +1,7 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DormantUsersQuery {
+    pub before: DateTime<Utc>,
+}
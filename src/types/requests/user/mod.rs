@@ -1 +1,6 @@
+pub mod dormant_users_query;
+pub mod update_password_request;
+pub mod update_subscription_request;
 pub mod update_user_request;
+pub mod update_user_status_request;
+pub mod user_search_query;
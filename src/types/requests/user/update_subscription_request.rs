@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+use crate::types::models::user::subscription::SubscriptionPlan;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSubscriptionRequest {
+    pub plan: SubscriptionPlan,
+}
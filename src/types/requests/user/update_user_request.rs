@@ -1,7 +1,18 @@
-use crate::types::models::user::subscription::SubscriptionPlan;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use validator::Validate;
 
+/// Fields that have their own endpoint or are maintained by the server.
+pub const IMMUTABLE_USER_FIELDS: [&str; 6] = [
+    "_id",
+    "email",
+    "password",
+    "subscription_plan",
+    "created_at",
+    "updated_at",
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone, Validate)]
 pub struct UpdateUserRequest {
     #[validate(length(
@@ -9,17 +20,65 @@ pub struct UpdateUserRequest {
         max = 50,
         message = "Username must be between 3 and 50 characters"
     ))]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
-    #[validate(email(message = "Invalid email format"))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub email: Option<String>,
+    /// The `version` last read by the client. When given, the update only
+    /// applies if nobody changed the user in the meantime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
 
-    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password: Option<String>,
+    #[serde(flatten, skip_serializing)]
+    pub extra: HashMap<String, Value>,
+}
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub subscription_plan: Option<SubscriptionPlan>,
+impl UpdateUserRequest {
+    pub fn immutable_field(&self) -> Option<&'static str> {
+        IMMUTABLE_USER_FIELDS
+            .iter()
+            .find(|field| self.extra.contains_key(**field))
+            .copied()
+    }
+
+    /// A field that is neither updatable nor one of the immutable ones,
+    /// typically a typo such as `nmae`.
+    pub fn unknown_field(&self) -> Option<&str> {
+        self.extra
+            .keys()
+            .filter(|field| !IMMUTABLE_USER_FIELDS.contains(&field.as_str()))
+            .min()
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn subscription_plan_is_not_editable_through_a_profile_update() {
+        let request: UpdateUserRequest =
+            serde_json::from_value(json!({ "name": "Ana", "subscription_plan": "Enterprise" }))
+                .unwrap();
+
+        assert_eq!(request.immutable_field(), Some("subscription_plan"));
+    }
+
+    #[test]
+    fn name_and_version_are_editable() {
+        let request: UpdateUserRequest =
+            serde_json::from_value(json!({ "name": "Ana", "version": 3 })).unwrap();
+
+        assert_eq!(request.immutable_field(), None);
+        assert_eq!(request.unknown_field(), None);
+    }
+
+    #[test]
+    fn misspelled_fields_are_reported_as_unknown() {
+        let request: UpdateUserRequest =
+            serde_json::from_value(json!({ "nmae": "Ana", "email": "ana@example.com" })).unwrap();
+
+        assert_eq!(request.unknown_field(), Some("nmae"));
+    }
 }
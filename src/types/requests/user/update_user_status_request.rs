@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+use crate::types::models::user::user_status::UserStatus;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserStatusRequest {
+    pub status: UserStatus,
+}
@@ -0,0 +1,93 @@
+use bson::{Document, doc};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::types::models::user::{subscription::SubscriptionPlan, user_status::UserStatus};
+
+const SORTABLE_FIELDS: [&str; 2] = ["created_at", "name"];
+
+#[derive(Debug, Deserialize)]
+pub struct UserSearchQuery {
+    pub search: Option<String>,
+
+    pub sort: Option<String>,
+
+    pub plan: Option<String>,
+
+    pub status: Option<String>,
+}
+
+impl UserSearchQuery {
+    pub fn term(&self) -> Option<&str> {
+        self.search
+            .as_deref()
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+    }
+
+    /// Without an explicit `sort`, search results are ordered by name and the
+    /// full listing by insertion order.
+    pub fn sort_document(&self) -> Result<Document, String> {
+        let Some(sort) = self.sort.as_deref().filter(|sort| !sort.trim().is_empty()) else {
+            return Ok(match self.term() {
+                Some(_) => doc! { "name": 1 },
+                None => doc! { "_id": 1 },
+            });
+        };
+
+        let mut document = Document::new();
+        for key in sort.split(',').map(str::trim) {
+            let (field, direction) = match key.strip_prefix('-') {
+                Some(field) => (field, -1),
+                None => (key, 1),
+            };
+            if !SORTABLE_FIELDS.contains(&field) || document.contains_key(field) {
+                return Err(key.to_string());
+            }
+            document.insert(field, direction);
+        }
+        document.insert("_id", 1);
+
+        Ok(document)
+    }
+
+    /// Combines the search term with the `plan` and `status` filters. An
+    /// unknown enum value is returned as `(param, value)`.
+    pub fn filter_document(&self) -> Result<Document, (&'static str, String)> {
+        let mut filter = Document::new();
+
+        if let Some(term) = self.term() {
+            let pattern = regex::escape(term);
+            filter.insert(
+                "$or",
+                vec![
+                    doc! { "name": { "$regex": &pattern, "$options": "i" } },
+                    doc! { "email": { "$regex": &pattern, "$options": "i" } },
+                ],
+            );
+        }
+        if let Some(plan) = enum_filter::<SubscriptionPlan>("plan", self.plan.as_deref())? {
+            filter.insert("subscription_plan", plan);
+        }
+        if let Some(status) = enum_filter::<UserStatus>("status", self.status.as_deref())? {
+            filter.insert("status", status);
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Checks `value` against the variants of `T`, which are stored under their
+/// serialized names, and returns it ready to be matched in a filter.
+fn enum_filter<T: DeserializeOwned>(
+    param: &'static str,
+    value: Option<&str>,
+) -> Result<Option<String>, (&'static str, String)> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+
+    serde_json::from_value::<T>(Value::String(value.to_string()))
+        .map(|_| Some(value.to_string()))
+        .map_err(|_| (param, value.to_string()))
+}
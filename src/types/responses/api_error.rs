@@ -0,0 +1,156 @@
+use actix_web::{HttpResponse, ResponseError, http::StatusCode};
+use serde_json::{Map, Value, json};
+use std::fmt;
+use validator::ValidationErrors;
+
+use crate::types::responses::api_response::{ApiResponse, ErrorDetails};
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest {
+        message: String,
+        details: Option<Value>,
+    },
+    Unauthorized(String),
+    TokenExpired(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    PayloadTooLarge(String),
+    TooManyRequests(String),
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn bad_request(message: impl Into<String>, details: Option<Value>) -> Self {
+        Self::BadRequest {
+            message: message.into(),
+            details,
+        }
+    }
+
+    pub fn validation(errors: ValidationErrors, message: impl Into<String>) -> Self {
+        Self::bad_request(message, Some(field_messages(&errors)))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::BadRequest { message, .. } => message,
+            Self::Unauthorized(message)
+            | Self::TokenExpired(message)
+            | Self::Forbidden(message)
+            | Self::NotFound(message)
+            | Self::Conflict(message)
+            | Self::PayloadTooLarge(message)
+            | Self::TooManyRequests(message)
+            | Self::Internal(message) => message,
+        }
+    }
+}
+
+/// Flattens `ValidationErrors` into `{ field: [{ code, message }] }` so clients
+/// can show each message next to its field.
+fn field_messages(errors: &ValidationErrors) -> Value {
+    let fields: Map<String, Value> = errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, field_errors)| {
+            let entries = field_errors
+                .iter()
+                .map(|error| {
+                    json!({
+                        "code": error.code,
+                        "message": error.message.as_deref().unwrap_or(&error.code),
+                    })
+                })
+                .collect();
+            (field.to_string(), Value::Array(entries))
+        })
+        .collect();
+
+    Value::Object(fields)
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            Self::Unauthorized(_) | Self::TokenExpired(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let details = match self {
+            Self::BadRequest { details, .. } => details.clone(),
+            Self::TokenExpired(_) => Some(json!({ "code": "token_expired" })),
+            Self::PayloadTooLarge(_) => Some(json!({ "code": "payload_too_large" })),
+            _ => None,
+        };
+
+        HttpResponse::build(self.status_code()).json(ApiResponse::<()>::error(
+            self.message(),
+            details.map(|details| ErrorDetails {
+                details: Some(details),
+            }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+
+    async fn rendered(error: ApiError) -> (StatusCode, Value) {
+        let response = error.error_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn not_found_renders_only_the_message() {
+        let (status, body) = rendered(ApiError::NotFound("Organization not found.".into())).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, json!({ "message": "Organization not found." }));
+    }
+
+    #[actix_rt::test]
+    async fn bad_request_renders_its_details_under_error() {
+        let (status, body) = rendered(ApiError::bad_request(
+            "Invalid member data.",
+            Some(json!({ "field": "name" })),
+        ))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body,
+            json!({
+                "message": "Invalid member data.",
+                "error": { "details": { "field": "name" } },
+            })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn bad_request_without_details_has_no_error_object() {
+        let (status, body) = rendered(ApiError::bad_request("Invalid id.", None)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.get("error").is_none());
+    }
+}
@@ -33,3 +33,52 @@ impl<T> ApiResponse<T> {
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(items: Vec<T>, total: u64, page: u64, per_page: u64) -> Self {
+        PaginatedResponse {
+            items,
+            total,
+            page,
+            per_page,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedResponse<U> {
+        PaginatedResponse {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn paginated_responses_serialize_inside_the_success_envelope() {
+        let page = PaginatedResponse::new(vec!["a", "b"], 7, 2, 2);
+
+        let body = serde_json::to_value(ApiResponse::success("Fetched.", page)).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "message": "Fetched.",
+                "data": { "items": ["a", "b"], "total": 7, "page": 2, "per_page": 2 },
+            })
+        );
+    }
+}
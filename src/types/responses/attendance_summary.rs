@@ -0,0 +1,19 @@
+use bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AttendanceSummary {
+    pub user_id: ObjectId,
+
+    #[serde(default)]
+    pub present: u32,
+
+    #[serde(default)]
+    pub absent: u32,
+
+    #[serde(default)]
+    pub late: u32,
+
+    #[serde(default)]
+    pub excused: u32,
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchAttendanceEntry {
+    pub index: usize,
+
+    pub success: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct BatchAttendanceResponse {
+    pub inserted: usize,
+
+    pub skipped: usize,
+
+    pub entries: Vec<BatchAttendanceEntry>,
+}
+
+impl BatchAttendanceResponse {
+    pub fn push_success(&mut self, index: usize) {
+        self.inserted += 1;
+        self.entries.push(BatchAttendanceEntry {
+            index,
+            success: true,
+            error: None,
+        });
+    }
+
+    pub fn push_skipped(&mut self, index: usize, error: String) {
+        self.skipped += 1;
+        self.entries.push(BatchAttendanceEntry {
+            index,
+            success: false,
+            error: Some(error),
+        });
+    }
+}
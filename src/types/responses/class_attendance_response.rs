@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClassAttendanceResult {
+    pub member_id: String,
+
+    pub success: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ClassAttendanceResponse {
+    pub marked: usize,
+
+    pub rejected: usize,
+
+    pub results: Vec<ClassAttendanceResult>,
+}
+
+impl ClassAttendanceResponse {
+    pub fn push_success(&mut self, member_id: String) {
+        self.marked += 1;
+        self.results.push(ClassAttendanceResult {
+            member_id,
+            success: true,
+            error: None,
+        });
+    }
+
+    pub fn push_rejected(&mut self, member_id: String, error: String) {
+        self.rejected += 1;
+        self.results.push(ClassAttendanceResult {
+            member_id,
+            success: false,
+            error: Some(error),
+        });
+    }
+}
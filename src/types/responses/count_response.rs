@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct CountResponse {
+    pub count: u64,
+}
+
+impl From<u64> for CountResponse {
+    fn from(count: u64) -> Self {
+        Self { count }
+    }
+}
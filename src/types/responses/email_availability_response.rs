@@ -0,0 +1,6 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct EmailAvailabilityResponse {
+    pub available: bool,
+}
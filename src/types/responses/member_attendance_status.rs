@@ -0,0 +1,19 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::models::{attendance::attendance_status::AttendanceStatus, user::role::Role};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberAttendanceStatus {
+    pub member_id: ObjectId,
+
+    pub name: String,
+
+    pub role: Role,
+
+    pub status: AttendanceStatus,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_in: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+use crate::types::responses::attendance_summary::AttendanceSummary;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MemberAttendanceSummary {
+    #[serde(flatten)]
+    pub summary: AttendanceSummary,
+
+    pub total: u32,
+
+    pub attendance_percentage: f64,
+}
+
+impl MemberAttendanceSummary {
+    pub fn new(summary: AttendanceSummary) -> Self {
+        let total = summary.present + summary.absent + summary.late + summary.excused;
+        let attended = summary.present + summary.late;
+        let attendance_percentage = if total == 0 {
+            0.0
+        } else {
+            (f64::from(attended) / f64::from(total) * 10_000.0).round() / 100.0
+        };
+
+        Self {
+            summary,
+            total,
+            attendance_percentage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::oid::ObjectId;
+    use serde_json::json;
+
+    #[test]
+    fn member_summaries_serialize_flat_with_totals() {
+        let user_id = ObjectId::parse_str("665f1f77bcf86cd799439021").unwrap();
+        let summary = MemberAttendanceSummary::new(AttendanceSummary {
+            user_id,
+            present: 1,
+            absent: 1,
+            late: 1,
+            excused: 0,
+        });
+
+        let body = serde_json::to_value(summary).unwrap();
+
+        assert_eq!(
+            body["user_id"],
+            json!({ "$oid": "665f1f77bcf86cd799439021" })
+        );
+        assert_eq!(body["present"], 1);
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["attendance_percentage"], 66.67);
+    }
+
+    #[test]
+    fn members_without_records_have_a_zero_percentage() {
+        let summary = MemberAttendanceSummary::new(AttendanceSummary::default());
+
+        assert_eq!(summary.total, 0);
+        assert_eq!(summary.attendance_percentage, 0.0);
+    }
+}
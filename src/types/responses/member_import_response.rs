@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MemberImportRow {
+    pub line: usize,
+
+    pub success: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MemberImportResponse {
+    pub imported: usize,
+
+    pub failed: usize,
+
+    pub rows: Vec<MemberImportRow>,
+}
+
+impl MemberImportResponse {
+    pub fn push_success(&mut self, line: usize) {
+        self.imported += 1;
+        self.rows.push(MemberImportRow {
+            line,
+            success: true,
+            error: None,
+        });
+    }
+
+    pub fn push_failure(&mut self, line: usize, error: String) {
+        self.failed += 1;
+        self.rows.push(MemberImportRow {
+            line,
+            success: false,
+            error: Some(error),
+        });
+    }
+}
@@ -1 +1,11 @@
+pub mod api_error;
 pub mod api_response;
+pub mod attendance_summary;
+pub mod batch_attendance_response;
+pub mod class_attendance_response;
+pub mod count_response;
+pub mod email_availability_response;
+pub mod member_attendance_status;
+pub mod member_attendance_summary;
+pub mod member_import_response;
+pub mod user_response;
@@ -0,0 +1,60 @@
+use bson::oid::ObjectId;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::{
+    models::user_model::User,
+    types::models::user::{subscription::SubscriptionPlan, user_status::UserStatus},
+};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UserResponse {
+    pub _id: Option<ObjectId>,
+
+    pub name: String,
+
+    pub email: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_display: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    pub organization_ids: HashSet<ObjectId>,
+
+    pub owned_organizations: u32,
+
+    pub subscription_plan: SubscriptionPlan,
+
+    pub status: UserStatus,
+
+    pub email_verified: bool,
+
+    pub version: u32,
+
+    pub created_at: DateTime<Utc>,
+
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            _id: user._id,
+            name: user.name,
+            email: user.email,
+            email_display: user.email_display,
+            username: user.username,
+            organization_ids: user.organization_ids,
+            owned_organizations: user.owned_organizations,
+            subscription_plan: user.subscription_plan,
+            status: user.status,
+            email_verified: user.email_verified,
+            version: user.version,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
@@ -1,36 +1,91 @@
-use crate::constants::{COOKIE_NAME, JWT_SECRET_KEY};
-use crate::types::auth::claims::Claims;
+use crate::constants::{
+    ARGON2_ITERATIONS, ARGON2_MEMORY_KIB, ARGON2_PARALLELISM, COOKIE_NAME, COOKIE_SAMESITE,
+    COOKIE_SECURE, EMAIL_VERIFICATION_EXPIRY_SECS, JWT_AUDIENCE, JWT_EXPIRY_SECS, JWT_ISSUER,
+    JWT_SECRET_KEY, LOGIN_LOCKOUT_SECS, LOGIN_LOCKOUT_THRESHOLD, PASSWORD_RESET_EXPIRY_SECS,
+    REQUIRE_EMAIL_VERIFICATION,
+};
+use crate::types::auth::{
+    claims::Claims, jwt_error::JwtError, verification_claims::VerificationClaims,
+};
 use actix_web::cookie::time::Duration as CookieDuration;
 use actix_web::cookie::{Cookie, SameSite};
 use argon2::{
+    Algorithm as Argon2Algorithm, Argon2, Params, Version,
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
 };
 use chrono::{Duration as ChronoDuration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode,
+    errors::ErrorKind as JwtErrorKind,
+};
 use log::{error, info};
 use phonenumber::{country, parse};
-use rand::rngs::OsRng;
+use rand::{RngCore, rngs::OsRng};
+use std::{ops::RangeInclusive, sync::LazyLock};
 use validator::ValidationError;
 
+const EMAIL_VERIFICATION_AUDIENCE: &str = "attendx-email-verification";
+
+fn cookie_secure() -> bool {
+    parse_secure(&COOKIE_SECURE)
+}
+
+fn cookie_same_site() -> SameSite {
+    parse_same_site(&COOKIE_SAMESITE)
+}
+
+fn parse_secure(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true") || value == "1"
+}
+
+fn parse_same_site(value: &str) -> SameSite {
+    match value.to_ascii_lowercase().as_str() {
+        "none" => SameSite::None,
+        "lax" => SameSite::Lax,
+        _ => SameSite::Strict,
+    }
+}
+
 pub fn generate_cookie(token: String) -> Cookie<'static> {
     Cookie::build(COOKIE_NAME.as_str(), token)
         .http_only(true)
-        .secure(true)
-        .same_site(SameSite::Strict)
+        .secure(cookie_secure())
+        .same_site(cookie_same_site())
+        .path("/")
+        .max_age(CookieDuration::seconds(jwt_expiry().num_seconds()))
+        .finish()
+}
+
+pub fn generate_expired_cookie() -> Cookie<'static> {
+    Cookie::build(COOKIE_NAME.as_str(), "")
+        .http_only(true)
+        .secure(cookie_secure())
+        .same_site(cookie_same_site())
         .path("/")
-        .max_age(CookieDuration::days(7))
+        .max_age(CookieDuration::ZERO)
         .finish()
 }
 
-pub fn generate_jwt(name: &str, email: &str) -> Result<String, String> {
+pub fn jwt_expiry() -> ChronoDuration {
+    JWT_EXPIRY_SECS
+        .parse()
+        .map(ChronoDuration::seconds)
+        .unwrap_or_else(|_| ChronoDuration::hours(24))
+}
+
+pub fn generate_jwt(name: &str, email: &str, token_version: u32) -> Result<String, String> {
     let secret_key = JWT_SECRET_KEY.as_bytes();
-    let expiration = Utc::now() + ChronoDuration::hours(24);
+    let issued_at = Utc::now();
+    let expiration = issued_at + jwt_expiry();
 
     let claims = Claims {
         name: name.to_owned(),
         email: email.to_owned(),
         exp: expiration.timestamp() as usize,
+        iat: issued_at.timestamp() as usize,
+        iss: JWT_ISSUER.to_string(),
+        aud: JWT_AUDIENCE.to_string(),
+        ver: token_version,
     };
 
     info!("✅ Claims created successfully");
@@ -46,22 +101,157 @@ pub fn generate_jwt(name: &str, email: &str) -> Result<String, String> {
     })
 }
 
-pub fn verify_jwt(token: &str) -> Result<Claims, String> {
+pub fn verify_jwt(token: &str) -> Result<Claims, JwtError> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_issuer(&[JWT_ISSUER.as_str()]);
+    validation.set_audience(&[JWT_AUDIENCE.as_str()]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
     decode::<Claims>(
         token,
         &DecodingKey::from_secret(JWT_SECRET_KEY.as_bytes()),
-        &Validation::new(Algorithm::HS256),
+        &validation,
+    )
+    .map(|data| data.claims)
+    .map_err(|err| match err.kind() {
+        JwtErrorKind::ExpiredSignature => JwtError::Expired,
+        _ => {
+            error!("❌ Error verifying JWT: {:?}", err);
+            JwtError::Invalid
+        }
+    })
+}
+
+pub fn login_lockout_threshold() -> u32 {
+    LOGIN_LOCKOUT_THRESHOLD.parse().unwrap_or(5)
+}
+
+pub fn login_lockout_duration() -> ChronoDuration {
+    LOGIN_LOCKOUT_SECS
+        .parse()
+        .map(ChronoDuration::seconds)
+        .unwrap_or_else(|_| ChronoDuration::minutes(15))
+}
+
+pub fn email_verification_required() -> bool {
+    REQUIRE_EMAIL_VERIFICATION.eq_ignore_ascii_case("true")
+        || REQUIRE_EMAIL_VERIFICATION.as_str() == "1"
+}
+
+/// Signs a token proving ownership of `email`. It uses its own audience, so it
+/// is never accepted as a session token and vice versa.
+pub fn generate_verification_token(email: &str) -> Result<String, String> {
+    let issued_at = Utc::now();
+    let expiry = EMAIL_VERIFICATION_EXPIRY_SECS
+        .parse()
+        .map(ChronoDuration::seconds)
+        .unwrap_or_else(|_| ChronoDuration::hours(24));
+
+    let claims = VerificationClaims {
+        email: email.to_owned(),
+        exp: (issued_at + expiry).timestamp() as usize,
+        iat: issued_at.timestamp() as usize,
+        iss: JWT_ISSUER.to_string(),
+        aud: EMAIL_VERIFICATION_AUDIENCE.to_string(),
+    };
+
+    encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(JWT_SECRET_KEY.as_bytes()),
+    )
+    .map_err(|e| {
+        error!("❌ Error generating verification token: {:?}", e);
+        format!("Verification token generation failed: {}", e)
+    })
+}
+
+pub fn verify_verification_token(token: &str) -> Result<VerificationClaims, JwtError> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_issuer(&[JWT_ISSUER.as_str()]);
+    validation.set_audience(&[EMAIL_VERIFICATION_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+    decode::<VerificationClaims>(
+        token,
+        &DecodingKey::from_secret(JWT_SECRET_KEY.as_bytes()),
+        &validation,
     )
     .map(|data| data.claims)
-    .map_err(|err| {
-        error!("❌ Error verifying JWT: {:?}", err);
-        "Error verifying JWT".to_string()
+    .map_err(|err| match err.kind() {
+        JwtErrorKind::ExpiredSignature => JwtError::Expired,
+        _ => JwtError::Invalid,
     })
 }
 
+pub fn password_reset_expiry() -> ChronoDuration {
+    PASSWORD_RESET_EXPIRY_SECS
+        .parse()
+        .map(ChronoDuration::seconds)
+        .unwrap_or_else(|_| ChronoDuration::minutes(30))
+}
+
+pub fn generate_reset_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn parse_cost(name: &str, value: &str, range: RangeInclusive<u32>) -> Result<u32, String> {
+    let parsed: u32 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{name} must be a whole number, got '{value}'"))?;
+
+    if !range.contains(&parsed) {
+        return Err(format!(
+            "{name} must be between {} and {}, got {parsed}",
+            range.start(),
+            range.end()
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Reads the Argon2id costs from `ARGON2_MEMORY_KIB`, `ARGON2_ITERATIONS` and
+/// `ARGON2_PARALLELISM`, rejecting values outside a sane range. Called at
+/// startup so a bad deployment config fails before serving traffic.
+///
+/// Hashing time grows linearly with memory and iterations, and every login,
+/// registration and password change pays it. Each concurrent hash also holds
+/// `ARGON2_MEMORY_KIB` of RAM, so raise the costs only as far as the host can
+/// sustain at peak login volume.
+pub fn load_password_hash_params() -> Result<Params, String> {
+    let memory = parse_cost(
+        "ARGON2_MEMORY_KIB",
+        &ARGON2_MEMORY_KIB,
+        8 * 1024..=1024 * 1024,
+    )?;
+    let iterations = parse_cost("ARGON2_ITERATIONS", &ARGON2_ITERATIONS, 1..=32)?;
+    let parallelism = parse_cost("ARGON2_PARALLELISM", &ARGON2_PARALLELISM, 1..=16)?;
+
+    Params::new(memory, iterations, parallelism, None)
+        .map_err(|err| format!("Invalid Argon2 parameters: {err}"))
+}
+
+pub fn password_hash_params() -> Params {
+    static PARAMS: LazyLock<Params> = LazyLock::new(|| {
+        load_password_hash_params().unwrap_or_else(|err| {
+            error!("❌ {}, using defaults", err);
+            Params::default()
+        })
+    });
+
+    PARAMS.clone()
+}
+
 pub fn hash_password(password: &str) -> Result<String, String> {
+    hash_password_with_params(password, password_hash_params())
+}
+
+pub fn hash_password_with_params(password: &str, params: Params) -> Result<String, String> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let argon2 = Argon2::new(Argon2Algorithm::Argon2id, Version::V0x13, params);
 
     argon2
         .hash_password(password.as_bytes(), &salt)
@@ -78,14 +268,121 @@ pub fn verify_password(password: &str, password_hash: &str) -> Result<bool, Stri
         "Invalid hash format".to_string()
     })?;
 
+    // The cost parameters are read from the stored hash, so hashes created
+    // under older settings keep verifying after the env values change.
     let argon2 = Argon2::default();
     Ok(argon2
         .verify_password(password.as_bytes(), &parsed_hash)
         .is_ok())
 }
 
+pub fn password_needs_rehash(password_hash: &str) -> bool {
+    let Ok(parsed_hash) = PasswordHash::new(password_hash) else {
+        return false;
+    };
+
+    if parsed_hash.algorithm != Argon2Algorithm::Argon2id.ident() {
+        return true;
+    }
+
+    let Ok(stored) = Params::try_from(&parsed_hash) else {
+        return false;
+    };
+    let target = password_hash_params();
+
+    stored.m_cost() < target.m_cost()
+        || stored.t_cost() < target.t_cost()
+        || stored.p_cost() < target.p_cost()
+}
+
+/// Key used for storing and looking up accounts: trimmed and fully lowercased,
+/// so addresses differing only in case belong to one account. The casing the
+/// user typed is kept separately in `email_display`.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
 pub fn validate_phone_number(phone: &str) -> Result<(), ValidationError> {
     parse(Some(country::ID), phone)
         .map(|_| ())
         .map_err(|_| ValidationError::new("invalid_phone_number"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::in_memory_user_repository::init_test_env;
+
+    #[test]
+    fn cookie_settings_are_parsed_from_their_env_values() {
+        assert!(parse_secure("true"));
+        assert!(parse_secure("1"));
+        assert!(!parse_secure("false"));
+        assert_eq!(parse_same_site("None"), SameSite::None);
+        assert_eq!(parse_same_site("lax"), SameSite::Lax);
+        assert_eq!(parse_same_site("anything-else"), SameSite::Strict);
+    }
+
+    #[test]
+    fn session_cookies_honor_the_configured_policy() {
+        init_test_env();
+
+        let cookie = generate_cookie("token".to_string());
+        let expired = generate_expired_cookie();
+
+        for cookie in [&cookie, &expired] {
+            assert_eq!(cookie.secure(), Some(parse_secure(&COOKIE_SECURE)));
+            assert_eq!(cookie.same_site(), Some(parse_same_site(&COOKIE_SAMESITE)));
+            assert_eq!(cookie.http_only(), Some(true));
+        }
+        if cfg!(debug_assertions) && std::env::var("COOKIE_SECURE").is_err() {
+            assert_eq!(cookie.secure(), Some(false));
+        }
+    }
+
+    #[test]
+    fn a_hash_with_a_higher_cost_still_verifies() {
+        let default = Params::default();
+        let higher = Params::new(default.m_cost() * 2, default.t_cost() + 1, 1, None).unwrap();
+
+        let hash = hash_password_with_params("correct horse", higher).unwrap();
+
+        assert!(hash.contains(&format!(
+            "m={},t={}",
+            default.m_cost() * 2,
+            default.t_cost() + 1
+        )));
+        assert!(verify_password("correct horse", &hash).unwrap());
+        assert!(!verify_password("battery staple", &hash).unwrap());
+    }
+
+    #[test]
+    fn hashes_made_under_two_cost_settings_both_verify() {
+        let cheap = Params::new(8 * 1024, 1, 1, None).unwrap();
+        let costly = Params::new(32 * 1024, 3, 1, None).unwrap();
+
+        let old_hash = hash_password_with_params("correct horse", cheap).unwrap();
+        let new_hash = hash_password_with_params("correct horse", costly).unwrap();
+
+        assert_ne!(old_hash, new_hash);
+        assert!(verify_password("correct horse", &old_hash).unwrap());
+        assert!(verify_password("correct horse", &new_hash).unwrap());
+    }
+
+    #[test]
+    fn normalizing_an_email_trims_and_case_folds_it() {
+        assert_eq!(
+            normalize_email("  Mixed.Case@Example.COM "),
+            "mixed.case@example.com"
+        );
+        assert_eq!(normalize_email("user@example.com"), "user@example.com");
+        assert_eq!(normalize_email(" not-an-email "), "not-an-email");
+    }
+
+    #[test]
+    fn costs_outside_the_allowed_range_are_rejected() {
+        assert_eq!(parse_cost("ARGON2_ITERATIONS", "3", 1..=32), Ok(3));
+        assert!(parse_cost("ARGON2_ITERATIONS", "0", 1..=32).is_err());
+        assert!(parse_cost("ARGON2_ITERATIONS", "lots", 1..=32).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+use std::{fmt, sync::LazyLock};
+
+use crate::constants::DEFAULT_LANG;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    Id,
+    De,
+    Ja,
+}
+
+impl Lang {
+    pub fn all() -> &'static [Lang] {
+        &[Lang::En, Lang::Id, Lang::De, Lang::Ja]
+    }
+
+    /// The ISO 639-1 code, which is also the folder name under `locales/`.
+    pub fn as_code(&self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Id => "id",
+            Lang::De => "de",
+            Lang::Ja => "ja",
+        }
+    }
+
+    /// The deployment-wide language from `DEFAULT_LANG`, used whenever a
+    /// request does not name one. The server refuses to start when the value
+    /// is unknown, see [`Lang::load_deployment_default`].
+    pub fn deployment_default() -> Self {
+        static DEFAULT: LazyLock<Lang> =
+            LazyLock::new(|| Lang::load_deployment_default().unwrap_or(Lang::En));
+        *DEFAULT
+    }
+
+    pub fn load_deployment_default() -> Result<Self, String> {
+        Self::parse(&DEFAULT_LANG)
+            .ok_or_else(|| format!("Unsupported DEFAULT_LANG: {}", *DEFAULT_LANG))
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        Self::parse(code).unwrap_or_else(Self::deployment_default)
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|lang| lang.as_code().eq_ignore_ascii_case(code))
+    }
+}
+
+impl Serialize for Lang {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_code())
+    }
+}
+
+/// Unlike [`Lang::from_code`], unknown codes are rejected rather than mapped
+/// to the deployment default, so stored settings are always a supported
+/// language.
+impl<'de> Deserialize<'de> for Lang {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Self::parse(&code).ok_or_else(|| {
+            let supported: Vec<&str> = Self::all().iter().map(Lang::as_code).collect();
+            D::Error::custom(format!(
+                "unsupported language '{code}', expected one of: {}",
+                supported.join(", ")
+            ))
+        })
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_language_round_trips_through_its_code() {
+        for &lang in Lang::all() {
+            assert_eq!(Lang::parse(lang.as_code()), Some(lang));
+            assert_eq!(Lang::from_code(lang.as_code()), lang);
+            assert_eq!(lang.to_string(), lang.as_code());
+        }
+    }
+
+    #[test]
+    fn the_default_language_is_english() {
+        assert_eq!(Lang::default(), Lang::En);
+    }
+
+    #[test]
+    fn the_deployment_default_falls_back_to_english() {
+        assert_eq!(DEFAULT_LANG.as_str(), "en");
+        assert_eq!(Lang::load_deployment_default(), Ok(Lang::En));
+    }
+}
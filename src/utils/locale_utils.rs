@@ -1,37 +1,14 @@
-use serde_json::Value;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::sync::LazyLock;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Lang {
-    En,
-    Id,
-    De,
-    Ja,
-}
-
-impl Lang {
-    pub fn from_code(code: &str) -> Self {
-        match code.to_ascii_lowercase().as_str() {
-            "id" => Self::Id,
-            "de" => Self::De,
-            "ja" => Self::Ja,
-            "en" => Self::En,
-            _ => Self::En,
-        }
-    }
-}
+pub use crate::utils::lang::Lang;
 
 fn load_message_file(lang: Lang, namespace: &str) -> Value {
-    let lang_folder = match lang {
-        Lang::En => "en",
-        Lang::De => "de",
-        Lang::Id => "id",
-        Lang::Ja => "ja",
-    };
-
     let file_path = Path::new("locales")
-        .join(lang_folder)
+        .join(lang.as_code())
         .join(format!("{namespace}.json"));
 
     match fs::read_to_string(&file_path) {
@@ -49,59 +26,132 @@ fn load_message_file(lang: Lang, namespace: &str) -> Value {
     }
 }
 
+pub fn flatten_json(value: &Value, prefix: String, map: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(obj) => {
+            for (k, v) in obj {
+                let new_prefix = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_json(v, new_prefix, map);
+            }
+        }
+        Value::String(s) => {
+            map.insert(prefix, s.clone());
+        }
+        _ => {}
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Namespace {
     Validation,
     User,
     Auth,
+    Attendance,
+    Member,
+    Organization,
 }
 
+/// One language's parsed locale files.
 #[derive(Debug)]
-pub struct Messages {
-    pub user: Value,
-    pub validation: Value,
-    pub auth: Value,
+struct Catalog {
+    user: Value,
+    validation: Value,
+    auth: Value,
+    attendance: Value,
+    member: Value,
+    organization: Value,
 }
 
-impl Messages {
-    pub fn new(lang: Lang) -> Self {
+impl Catalog {
+    fn load(lang: Lang) -> Self {
         Self {
             user: load_message_file(lang, "user"),
             validation: load_message_file(lang, "validation"),
             auth: load_message_file(lang, "auth"),
+            attendance: load_message_file(lang, "attendance"),
+            member: load_message_file(lang, "member"),
+            organization: load_message_file(lang, "organization"),
         }
     }
 
-    pub fn get(&self, namespace: &Namespace, path: &str) -> Option<&Value> {
-        let root = match namespace {
+    fn namespace(&self, namespace: &Namespace) -> &Value {
+        match namespace {
             Namespace::User => &self.user,
             Namespace::Validation => &self.validation,
             Namespace::Auth => &self.auth,
-        };
+            Namespace::Attendance => &self.attendance,
+            Namespace::Member => &self.member,
+            Namespace::Organization => &self.organization,
+        }
+    }
+}
 
-        let mut current = root;
-        for key in path.split('.') {
-            match current.get(key) {
-                Some(next) => {
-                    current = next;
-                }
-                None => {
-                    return None;
-                }
+/// Read once on first use; the locale files do not change while the server runs.
+static CATALOGS: LazyLock<HashMap<Lang, Catalog>> = LazyLock::new(|| {
+    Lang::all()
+        .iter()
+        .map(|&lang| (lang, Catalog::load(lang)))
+        .collect()
+});
+
+fn lookup(catalog: &'static Catalog, namespace: &Namespace, path: &str) -> Option<&'static Value> {
+    let mut current = catalog.namespace(namespace);
+    for key in path.split('.') {
+        match current.get(key) {
+            Some(next) => {
+                current = next;
+            }
+            None => {
+                return None;
             }
         }
+    }
+
+    Some(current)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Messages {
+    catalog: &'static Catalog,
+    fallback: Option<&'static Catalog>,
+}
+
+impl Messages {
+    pub fn new(lang: Lang) -> Self {
+        Self {
+            catalog: &CATALOGS[&lang],
+            fallback: (lang != Lang::En).then(|| &CATALOGS[&Lang::En]),
+        }
+    }
+
+    pub fn bundle(&self) -> Value {
+        json!({
+            "user": self.catalog.user,
+            "validation": self.catalog.validation,
+            "auth": self.catalog.auth,
+        })
+    }
 
-        Some(current)
+    pub fn get(&self, namespace: &Namespace, path: &str) -> Option<&'static Value> {
+        lookup(self.catalog, namespace, path)
     }
 
+    /// Looks the key up in the requested language, then in English, and only
+    /// then returns the literal `fallback`.
     pub fn get_str(&self, namespace: Namespace, path: &str, fallback: &str) -> String {
-        let result = self
-            .get(&namespace, path)
+        self.get(&namespace, path)
             .and_then(Value::as_str)
+            .or_else(|| {
+                self.fallback
+                    .and_then(|english| lookup(english, &namespace, path))
+                    .and_then(Value::as_str)
+            })
             .unwrap_or(fallback)
-            .to_string();
-
-        result
+            .to_string()
     }
 
     pub fn get_user_message(&self, key: &str, default: &str) -> String {
@@ -112,21 +162,159 @@ impl Messages {
         self.get_str(Namespace::Auth, key, default)
     }
 
+    pub fn get_attendance_message(&self, key: &str, default: &str) -> String {
+        self.get_str(Namespace::Attendance, key, default)
+    }
+
+    pub fn get_member_message(&self, key: &str, default: &str) -> String {
+        self.get_str(Namespace::Member, key, default)
+    }
+
+    pub fn get_organization_message(&self, key: &str, default: &str) -> String {
+        self.get_str(Namespace::Organization, key, default)
+    }
+
     pub fn get_validation_message(&self, key: &str, default: &str) -> String {
         self.get_str(Namespace::Validation, key, default)
     }
 }
 
 pub fn get_lang(req: &actix_web::HttpRequest) -> Lang {
+    query_lang(req)
+        .or_else(|| header_lang(req, "X-Lang"))
+        .or_else(|| header_lang(req, "Accept-Language"))
+        .map(|code| Lang::from_code(&code))
+        .unwrap_or_else(Lang::deployment_default)
+}
+
+fn query_lang(req: &actix_web::HttpRequest) -> Option<String> {
+    actix_web::web::Query::<std::collections::HashMap<String, String>>::from_query(
+        req.query_string(),
+    )
+    .ok()
+    .and_then(|query| query.get("lang").cloned())
+    .filter(|lang| !lang.trim().is_empty())
+}
+
+fn header_lang(req: &actix_web::HttpRequest, name: &str) -> Option<String> {
     req.headers()
-        .get("Accept-Language")
+        .get(name)
         .and_then(|value| value.to_str().ok())
         .and_then(|header| {
             header
                 .split(',')
                 .next()
-                .and_then(|tag| tag.split('-').next())
+                .and_then(|tag| tag.split(['-', ';']).next())
         })
-        .map(Lang::from_code)
-        .unwrap_or(Lang::De)
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use std::collections::BTreeSet;
+
+    fn flattened_keys(lang: Lang, namespace: &str) -> BTreeSet<String> {
+        let mut map = BTreeMap::new();
+        flatten_json(&load_message_file(lang, namespace), String::new(), &mut map);
+        map.into_keys().collect()
+    }
+
+    /// Returns the keys found in `locales/en` but absent from another language,
+    /// grouped by `<lang>/<namespace>`.
+    fn missing_translation_keys() -> BTreeMap<String, BTreeSet<String>> {
+        let namespaces: Vec<String> = fs::read_dir(Path::new("locales").join("en"))
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+
+        let mut missing = BTreeMap::new();
+        for namespace in &namespaces {
+            let source = flattened_keys(Lang::En, namespace);
+            for &lang in Lang::all().iter().filter(|lang| **lang != Lang::En) {
+                let translated = flattened_keys(lang, namespace);
+                let absent: BTreeSet<String> = source.difference(&translated).cloned().collect();
+                if !absent.is_empty() {
+                    missing.insert(format!("{lang}/{namespace}"), absent);
+                }
+            }
+        }
+        missing
+    }
+
+    #[test]
+    fn messages_share_the_catalog_parsed_on_first_use() {
+        let first = Messages::new(Lang::De);
+        let second = Messages::new(Lang::De);
+
+        assert!(std::ptr::eq(first.catalog, second.catalog));
+        assert!(std::ptr::eq(
+            first.fallback.unwrap(),
+            Messages::new(Lang::En).catalog
+        ));
+    }
+
+    #[test]
+    fn unknown_keys_fall_back_to_the_literal() {
+        let messages = Messages::new(Lang::De);
+
+        assert_ne!(
+            messages.get_user_message("fetch.not_found", "literal"),
+            "literal"
+        );
+        assert_eq!(
+            messages.get_user_message("no.such.key", "literal"),
+            "literal"
+        );
+    }
+
+    #[test]
+    fn the_lang_query_parameter_wins_over_both_headers() {
+        let req = TestRequest::with_uri("/?lang=ja")
+            .insert_header(("X-Lang", "de"))
+            .insert_header(("Accept-Language", "id-ID,id;q=0.9"))
+            .to_http_request();
+
+        assert_eq!(get_lang(&req), Lang::Ja);
+    }
+
+    #[test]
+    fn the_x_lang_header_wins_over_accept_language() {
+        let req = TestRequest::default()
+            .insert_header(("X-Lang", "de"))
+            .insert_header(("Accept-Language", "id-ID,id;q=0.9"))
+            .to_http_request();
+
+        assert_eq!(get_lang(&req), Lang::De);
+    }
+
+    #[test]
+    fn accept_language_is_used_when_nothing_overrides_it() {
+        let req = TestRequest::default()
+            .insert_header(("Accept-Language", "id-ID,id;q=0.9"))
+            .to_http_request();
+
+        assert_eq!(get_lang(&req), Lang::Id);
+    }
+
+    #[test]
+    fn an_unknown_language_falls_back_to_the_default() {
+        let req = TestRequest::with_uri("/?lang=xx").to_http_request();
+
+        assert_eq!(get_lang(&req), Lang::deployment_default());
+    }
+
+    #[test]
+    fn every_locale_contains_all_english_keys() {
+        let missing = missing_translation_keys();
+
+        assert!(missing.is_empty(), "missing translation keys: {missing:#?}");
+    }
 }
@@ -1,3 +1,6 @@
 pub mod auth_utils;
+pub mod lang;
 pub mod locale_utils;
+pub mod rate_limiter;
+pub mod time_utils;
 pub mod validation_utils;
@@ -0,0 +1,135 @@
+use actix_web::HttpRequest;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::constants::TRUSTED_PROXIES;
+
+/// Fixed-window request counter keyed by an arbitrary client identifier.
+///
+/// Expired windows are swept at most once per window, and at most `capacity`
+/// clients are tracked at a time; requests from new clients are refused while
+/// the table is full, so a flood of distinct keys cannot grow it without bound.
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    capacity: usize,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    windows: HashMap<String, (Instant, u32)>,
+    last_sweep: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration, capacity: usize) -> Self {
+        Self {
+            max_requests,
+            window,
+            capacity,
+            state: Mutex::new(LimiterState {
+                windows: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+
+    /// Counts a request for `key` and returns whether it is still within the limit.
+    pub fn check(&self, key: &str) -> bool {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if now.duration_since(state.last_sweep) >= self.window {
+            let window = self.window;
+            state
+                .windows
+                .retain(|_, (started, _)| now.duration_since(*started) < window);
+            state.last_sweep = now;
+        }
+
+        if !state.windows.contains_key(key) && state.windows.len() >= self.capacity {
+            return false;
+        }
+
+        let (started, count) = state.windows.entry(key.to_string()).or_insert((now, 0));
+        if now.duration_since(*started) >= self.window {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= self.max_requests {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+/// The address to rate limit a request by. Forwarding headers are only
+/// honored when the connection comes from a proxy listed in `TRUSTED_PROXIES`;
+/// anyone else could set them to dodge the limit.
+pub fn client_key(req: &HttpRequest) -> String {
+    let peer = req.peer_addr().map(|addr| addr.ip());
+
+    if peer.is_some_and(is_trusted_proxy)
+        && let Some(forwarded) = req.connection_info().realip_remote_addr()
+    {
+        return forwarded.to_string();
+    }
+
+    peer.map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn is_trusted_proxy(ip: IpAddr) -> bool {
+    TRUSTED_PROXIES
+        .split(',')
+        .filter_map(|proxy| proxy.trim().parse::<IpAddr>().ok())
+        .any(|proxy| proxy == ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn requests_beyond_the_limit_are_refused_until_the_window_ends() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60), 10);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start));
+        assert!(limiter.check_at("a", start));
+        assert!(!limiter.check_at("a", start));
+        assert!(limiter.check_at("b", start));
+        assert!(limiter.check_at("a", start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn new_clients_are_refused_while_the_table_is_full() {
+        let limiter = RateLimiter::new(5, Duration::from_secs(60), 2);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start));
+        assert!(limiter.check_at("b", start));
+        assert!(!limiter.check_at("c", start));
+        assert!(limiter.check_at("a", start));
+        assert!(limiter.check_at("c", start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn forwarding_headers_from_untrusted_peers_are_ignored() {
+        let req = TestRequest::default()
+            .peer_addr("203.0.113.7:4000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "198.51.100.1"))
+            .to_http_request();
+
+        assert_eq!(client_key(&req), "203.0.113.7");
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Days, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::constants::LATE_GRACE_MINUTES;
+
+pub fn local_day_bounds(tz: Tz, at: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    local_date_bounds(tz, at.with_timezone(&tz).date_naive())
+}
+
+pub fn local_date_bounds(tz: Tz, local_date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let next_date = local_date.checked_add_days(Days::new(1)).unwrap_or(local_date);
+
+    let start_of = |date: NaiveDate| {
+        tz.from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+    };
+
+    (start_of(local_date), start_of(next_date))
+}
+
+/// Midnight UTC of the day `at` falls on.
+pub fn utc_day_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
+
+/// How long after a class starts a check-in still counts as on time.
+pub fn late_grace_period() -> Duration {
+    LATE_GRACE_MINUTES
+        .parse()
+        .map(Duration::minutes)
+        .unwrap_or_else(|_| Duration::minutes(10))
+}
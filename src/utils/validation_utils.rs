@@ -1,4 +1,3 @@
-use actix_web::HttpResponse;
 use rayon::prelude::*;
 use serde_json::json;
 use std::sync::{Arc, Mutex};
@@ -6,16 +5,27 @@ use std::{borrow::Cow, collections::HashMap};
 use validator::{ValidationError, ValidationErrors};
 
 use crate::{
-    types::requests::auth::{login_request::LoginRequest, register_request::RegisterRequest},
-    types::responses::api_response::{ApiResponse, ErrorDetails},
+    types::requests::{
+        auth::{login_request::LoginRequest, register_request::RegisterRequest},
+        organization::{
+            create_organization_request::CreateOrganizationRequest,
+            update_organization_request::UpdateOrganizationRequest,
+        },
+    },
     utils::locale_utils::Messages,
-    validations::{email::validate_email, name::validate_name, password::validate_password},
+    validations::{
+        email::validate_email,
+        identifier::{validate_login_identifier, validate_nidn, validate_nim},
+        name::validate_name,
+        password::validate_password,
+        timezone::validate_timezone,
+    },
 };
 
 type FieldValidation<'a> = (
     &'static str,
     &'a str,
-    fn(&'a str, &Messages) -> Result<(), ValidationError>,
+    fn(&'a str, &Messages) -> Result<(), Vec<ValidationError>>,
 );
 
 pub fn validate_fields(
@@ -25,9 +35,11 @@ pub fn validate_fields(
     let errors = Arc::new(Mutex::new(ValidationErrors::new()));
 
     fields.par_iter().for_each(|(field, value, validator)| {
-        if let Err(error) = validator(value, messages) {
+        if let Err(field_errors) = validator(value, messages) {
             let mut errors_lock = errors.lock().unwrap();
-            errors_lock.add(field, error);
+            for error in field_errors {
+                errors_lock.add(field, error);
+            }
         }
     });
 
@@ -39,17 +51,6 @@ pub fn validate_fields(
     }
 }
 
-pub fn handle_validation_error(errors: ValidationErrors, msg: &str) -> HttpResponse {
-    let error_details = ErrorDetails {
-        details: Some(json!(&errors)),
-    };
-    HttpResponse::BadRequest().json(ApiResponse::<()>::error(msg, Some(error_details)))
-}
-
-pub fn handle_internal_error(err: impl ToString) -> HttpResponse {
-    HttpResponse::InternalServerError().json(ApiResponse::<()>::error(err.to_string(), None))
-}
-
 pub fn validate_register_data(
     data: &RegisterRequest,
     messages: &Messages,
@@ -70,13 +71,78 @@ pub fn validate_login_data(
 ) -> Result<(), ValidationErrors> {
     validate_fields(
         vec![
-            ("email", &data.email, validate_email),
+            ("identifier", &data.identifier, validate_login_identifier),
             ("password", &data.password, validate_password),
         ],
         messages,
     )
 }
 
+pub fn validate_organization_data(
+    data: &CreateOrganizationRequest,
+    messages: &Messages,
+) -> Result<(), ValidationErrors> {
+    validate_fields(
+        vec![
+            ("name", &data.name, validate_name),
+            ("email", &data.email, validate_email),
+            ("timezone", &data.timezone, validate_timezone),
+        ],
+        messages,
+    )
+}
+
+/// Validates only the fields present in the update.
+pub fn validate_organization_update(
+    data: &UpdateOrganizationRequest,
+    messages: &Messages,
+) -> Result<(), ValidationErrors> {
+    let mut fields: Vec<FieldValidation> = Vec::new();
+    if let Some(name) = &data.name {
+        fields.push(("name", name, validate_name));
+    }
+    if let Some(email) = &data.email {
+        fields.push(("email", email, validate_email));
+    }
+    if let Some(timezone) = &data.timezone {
+        fields.push(("timezone", timezone, validate_timezone));
+    }
+
+    validate_fields(fields, messages)
+}
+
+pub fn validate_member_identifiers(
+    identifiers: &HashMap<String, String>,
+    messages: &Messages,
+) -> Result<(), ValidationErrors> {
+    let mut fields: Vec<FieldValidation> = Vec::new();
+    if let Some(nim) = identifiers.get("nim") {
+        fields.push(("nim", nim, validate_nim));
+    }
+    if let Some(nidn) = identifiers.get("nidn") {
+        fields.push(("nidn", nidn, validate_nidn));
+    }
+
+    validate_fields(fields, messages)
+}
+
+/// Turns each failed rule into its own `ValidationError`, so the field
+/// serializes as an array with one entry per failure.
+pub fn collect_errors(
+    code: &'static str,
+    errors: Vec<String>,
+    field_value: &str,
+) -> Result<(), Vec<ValidationError>> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(errors
+        .into_iter()
+        .map(|message| add_error(code, message, field_value))
+        .collect())
+}
+
 pub fn add_error(code: &'static str, message: String, field_value: &str) -> ValidationError {
     ValidationError {
         code: code.into(),
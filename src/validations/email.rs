@@ -1,8 +1,7 @@
 use email_address::EmailAddress;
-use rayon::prelude::*;
 use validator::ValidationError;
 
-use crate::utils::{locale_utils::Messages, validation_utils::add_error};
+use crate::utils::{locale_utils::Messages, validation_utils::collect_errors};
 
 const MIN_EMAIL_LENGTH: usize = 5;
 const MAX_EMAIL_LENGTH: usize = 254;
@@ -103,13 +102,13 @@ fn has_no_leading_or_trailing_dot(email: &str, messages: &Messages) -> Result<()
 }
 
 fn domain_starts_without_dot(email: &str, messages: &Messages) -> Result<(), String> {
-    if let Some(domain) = get_domain(email) {
-        if domain.starts_with('.') {
-            return Err(messages.get_validation_message(
-                "email.domain_starts_with_dot",
-                "The domain part must not start with a dot",
-            ));
-        }
+    if let Some(domain) = get_domain(email)
+        && domain.starts_with('.')
+    {
+        return Err(messages.get_validation_message(
+            "email.domain_starts_with_dot",
+            "The domain part must not start with a dot",
+        ));
     }
     Ok(())
 }
@@ -141,37 +140,36 @@ fn is_structure_valid_domain(email: &str, messages: &Messages) -> Result<(), Str
 }
 
 fn has_valid_domain_segment_length(email: &str, messages: &Messages) -> Result<(), String> {
-    if let Some(domain) = get_domain(email) {
-        if let Some(first_dot_index) = domain.find('.') {
-            if first_dot_index < MIN_DOMAIN_SEGMENT_LENGTH {
-                return Err(messages.get_validation_message(
-                    "email.invalid_domain_length",
-                    &format!(
-                        "The domain part (after '@') must have at least {} characters before the first dot",
-                        MIN_DOMAIN_SEGMENT_LENGTH
-                    ),
-                ));
-            }
-        }
+    if let Some(domain) = get_domain(email)
+        && let Some(first_dot_index) = domain.find('.')
+        && first_dot_index < MIN_DOMAIN_SEGMENT_LENGTH
+    {
+        return Err(messages.get_validation_message(
+            "email.invalid_domain_length",
+            &format!(
+                "The domain part (after '@') must have at least {} characters before the first dot",
+                MIN_DOMAIN_SEGMENT_LENGTH
+            ),
+        ));
     }
     Ok(())
 }
 
 fn has_valid_tld_format(email: &str, messages: &Messages) -> Result<(), String> {
-    if let Some(domain) = get_domain(email) {
-        if let Some(last_dot_index) = domain.rfind('.') {
-            let tld = &domain[last_dot_index + 1..];
-            let tld_length = tld.len();
-            let all_alphabetic = tld.chars().all(|c| c.is_alphabetic());
-            if tld_length < MIN_TLD_LENGTH || !all_alphabetic {
-                return Err(messages.get_validation_message(
-                    "email.invalid_tld",
-                    &format!(
-                        "The TLD (after the last '.') must be at least {} characters long and alphabetic",
-                        MIN_TLD_LENGTH
-                    ),
-                ));
-            }
+    if let Some(domain) = get_domain(email)
+        && let Some(last_dot_index) = domain.rfind('.')
+    {
+        let tld = &domain[last_dot_index + 1..];
+        let tld_length = tld.len();
+        let all_alphabetic = tld.chars().all(|c| c.is_alphabetic());
+        if tld_length < MIN_TLD_LENGTH || !all_alphabetic {
+            return Err(messages.get_validation_message(
+                "email.invalid_tld",
+                &format!(
+                    "The TLD (after the last '.') must be at least {} characters long and alphabetic",
+                    MIN_TLD_LENGTH
+                ),
+            ));
         }
     }
     Ok(())
@@ -189,8 +187,8 @@ fn get_domain(email: &str) -> Option<&str> {
     email.split('@').nth(1)
 }
 
-pub fn validate_email(email: &str, messages: &Messages) -> Result<(), ValidationError> {
-    let validations = vec![
+pub fn validate_email(email: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
+    let validations = [
         has_min_length,
         has_max_length,
         has_at_and_dot,
@@ -206,20 +204,15 @@ pub fn validate_email(email: &str, messages: &Messages) -> Result<(), Validation
     ];
 
     let mut errors: Vec<String> = validations
-        .par_iter()
+        .iter()
         .filter_map(|validate| validate(email, messages).err())
         .collect();
 
-    if errors.is_empty() {
-        if let Err(msg) = is_overall_format_valid(email, messages) {
-            errors.push(msg);
-        }
-    }
-
-    if !errors.is_empty() {
-        let concatenated_errors = errors.join(", ");
-        return Err(add_error("email.invalid", concatenated_errors, email));
+    if errors.is_empty()
+        && let Err(msg) = is_overall_format_valid(email, messages)
+    {
+        errors.push(msg);
     }
 
-    Ok(())
+    collect_errors("email.invalid", errors, email)
 }
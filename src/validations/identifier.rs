@@ -0,0 +1,67 @@
+use validator::ValidationError;
+
+use crate::{
+    utils::{locale_utils::Messages, validation_utils::add_error},
+    validations::email::validate_email,
+};
+
+const NIM_LENGTH: usize = 10;
+const NIDN_LENGTH: usize = 10;
+
+fn is_fixed_length_number(value: &str, length: usize) -> bool {
+    value.len() == length && value.chars().all(|c| c.is_ascii_digit())
+}
+
+pub fn is_nim(value: &str) -> bool {
+    is_fixed_length_number(value, NIM_LENGTH)
+}
+
+pub fn validate_nim(nim: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
+    if is_fixed_length_number(nim, NIM_LENGTH) {
+        return Ok(());
+    }
+
+    Err(vec![add_error(
+        "nim.invalid",
+        messages.get_validation_message(
+            "nim.invalid",
+            &format!("NIM must be exactly {} digits", NIM_LENGTH),
+        ),
+        nim,
+    )])
+}
+
+pub fn validate_nidn(nidn: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
+    if is_fixed_length_number(nidn, NIDN_LENGTH) {
+        return Ok(());
+    }
+
+    Err(vec![add_error(
+        "nidn.invalid",
+        messages.get_validation_message(
+            "nidn.invalid",
+            &format!("NIDN must be exactly {} digits", NIDN_LENGTH),
+        ),
+        nidn,
+    )])
+}
+
+/// Email-shaped identifiers go through the full email rules; NIMs only need
+/// to be present, since they are matched exactly on lookup.
+pub fn validate_login_identifier(
+    identifier: &str,
+    messages: &Messages,
+) -> Result<(), Vec<ValidationError>> {
+    if identifier.contains('@') {
+        return validate_email(identifier, messages);
+    }
+    if !identifier.trim().is_empty() {
+        return Ok(());
+    }
+
+    Err(vec![add_error(
+        "identifier.empty",
+        messages.get_validation_message("identifier.empty", "Enter your email or student ID"),
+        identifier,
+    )])
+}
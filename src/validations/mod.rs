@@ -1,3 +1,5 @@
 pub mod email;
+pub mod identifier;
 pub mod name;
 pub mod password;
+pub mod timezone;
@@ -1,7 +1,9 @@
-use rayon::prelude::*;
 use validator::ValidationError;
 
-use crate::utils::{locale_utils::Messages, validation_utils::add_error};
+use crate::{
+    constants::NAME_ALLOWED_PUNCTUATION,
+    utils::{locale_utils::Messages, validation_utils::collect_errors},
+};
 
 const MIN_NAME_LENGTH: usize = 2;
 const MAX_NAME_LENGTH: usize = 100;
@@ -15,7 +17,7 @@ fn is_not_empty(name: &str, messages: &Messages) -> Result<(), String> {
 }
 
 fn has_min_length(name: &str, messages: &Messages) -> Result<(), String> {
-    if name.len() < MIN_NAME_LENGTH {
+    if name.chars().count() < MIN_NAME_LENGTH {
         Err(messages.get_validation_message(
             "name.too_short",
             &format!("Name must be at least {} characters long", MIN_NAME_LENGTH),
@@ -26,7 +28,7 @@ fn has_min_length(name: &str, messages: &Messages) -> Result<(), String> {
 }
 
 fn has_max_length(name: &str, messages: &Messages) -> Result<(), String> {
-    if name.len() > MAX_NAME_LENGTH {
+    if name.chars().count() > MAX_NAME_LENGTH {
         Err(messages.get_validation_message(
             "name.too_long",
             &format!("Name must be less than {} characters", MAX_NAME_LENGTH),
@@ -36,18 +38,24 @@ fn has_max_length(name: &str, messages: &Messages) -> Result<(), String> {
     }
 }
 
+/// Letters in any script and whitespace are always allowed, plus the
+/// punctuation listed in `NAME_ALLOWED_PUNCTUATION` (apostrophes and hyphens by
+/// default, for names like "O'Brien" or "Anne-Marie").
 fn has_valid_chars(name: &str, messages: &Messages) -> Result<(), String> {
-    if !name.chars().all(|c| c.is_alphabetic() || c.is_whitespace()) {
+    if !name
+        .chars()
+        .all(|c| c.is_alphabetic() || c.is_whitespace() || NAME_ALLOWED_PUNCTUATION.contains(c))
+    {
         Err(messages.get_validation_message(
             "name.invalid_chars",
-            "Name can only contain letters and spaces",
+            "Name can only contain letters, spaces, hyphens and apostrophes",
         ))
     } else {
         Ok(())
     }
 }
 
-pub fn validate_name(name: &str, messages: &Messages) -> Result<(), ValidationError> {
+pub fn validate_name(name: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
     let validations = [
         is_not_empty,
         has_min_length,
@@ -56,14 +64,9 @@ pub fn validate_name(name: &str, messages: &Messages) -> Result<(), ValidationEr
     ];
 
     let errors: Vec<String> = validations
-        .par_iter()
+        .iter()
         .filter_map(|f| f(name, messages).err())
         .collect();
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        let concatenated_errors = errors.join(", ");
-        Err(add_error("name.invalid", concatenated_errors, name))
-    }
+    collect_errors("name.invalid", errors, name)
 }
@@ -1,7 +1,6 @@
-use rayon::prelude::*;
 use validator::ValidationError;
 
-use crate::utils::{locale_utils::Messages, validation_utils::add_error};
+use crate::utils::{locale_utils::Messages, validation_utils::collect_errors};
 
 const MIN_PASSWORD_LENGTH: usize = 8;
 const MAX_PASSWORD_LENGTH: usize = 128;
@@ -84,8 +83,8 @@ fn has_special_char(password: &str, messages: &Messages) -> Result<(), String> {
     Ok(())
 }
 
-pub fn validate_password(password: &str, messages: &Messages) -> Result<(), ValidationError> {
-    let validations = vec![
+pub fn validate_password(password: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
+    let validations = [
         has_min_length,
         has_max_length,
         has_no_space,
@@ -96,14 +95,36 @@ pub fn validate_password(password: &str, messages: &Messages) -> Result<(), Vali
     ];
 
     let errors: Vec<String> = validations
-        .par_iter()
+        .iter()
         .filter_map(|validate_fn| validate_fn(password, messages).err())
         .collect();
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        let concatenated_errors = errors.join(", ");
-        Err(add_error("password.invalid", concatenated_errors, password))
+    collect_errors("password.invalid", errors, password)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{lang::Lang, validation_utils::validate_fields};
+    use std::collections::HashSet;
+
+    #[test]
+    fn each_failed_rule_is_reported_as_its_own_error() {
+        let messages = Messages::new(Lang::En);
+
+        let errors = validate_fields(
+            vec![("password", "lowercaseonly", validate_password)],
+            &messages,
+        )
+        .unwrap_err();
+
+        let body = serde_json::to_value(&errors).unwrap();
+        let password_errors = body["password"].as_array().unwrap();
+        assert_eq!(password_errors.len(), 3);
+        let messages: HashSet<&str> = password_errors
+            .iter()
+            .map(|error| error["message"].as_str().unwrap())
+            .collect();
+        assert_eq!(messages.len(), 3, "three distinct messages: {messages:?}");
     }
 }
@@ -0,0 +1,19 @@
+use chrono_tz::Tz;
+use validator::ValidationError;
+
+use crate::utils::{locale_utils::Messages, validation_utils::add_error};
+
+pub fn validate_timezone(timezone: &str, messages: &Messages) -> Result<(), Vec<ValidationError>> {
+    if timezone.parse::<Tz>().is_ok() {
+        return Ok(());
+    }
+
+    Err(vec![add_error(
+        "timezone.invalid",
+        messages.get_validation_message(
+            "timezone.invalid",
+            "Timezone must be a valid IANA name such as 'Asia/Jakarta'",
+        ),
+        timezone,
+    )])
+}